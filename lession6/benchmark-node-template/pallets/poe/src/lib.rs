@@ -1,7 +1,15 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 /// A module for proof of existence
+///
+/// A claim has a single owner, or several co-owners of which a threshold has to approve before it is
+/// revoked or transferred. Claims change hands with `transfer_claim`, the new owner owns it alone.
+///
+/// A claim is at most `MaxProofLength` bytes. With `DigestClaimsOnly` every claim has to be a
+/// `TaggedDigest`, the hash algorithm and the 32 byte digest of a document, so a document can not
+/// be notarised again under another encoding of its hash.
 
+use codec::{Encode, Decode, DecodeAll};
 use frame_support::{
 	decl_module,
 	decl_storage,
@@ -9,12 +17,16 @@ use frame_support::{
 	decl_error,
 	ensure,
 	dispatch::{DispatchResult},
+	storage::migration::StorageIterator,
+	traits::Get,
+	weights::Weight,
 };
 use frame_system::{
 	self as system,
 	ensure_signed,
 };
-use sp_std::vec::Vec;
+use sp_runtime::RuntimeDebug;
+use sp_std::{prelude::*, mem};
 
 #[cfg(test)]
 mod mock;
@@ -22,16 +34,66 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+/// Version of the pallet's storage, bumped whenever existing entries need a migration.
+const STORAGE_VERSION: u32 = 2;
+
 /// The pallet's configuration trait.
 pub trait Trait: system::Trait {
 	/// The overarching event type.
 	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+	/// Maximum number of co-owners of a claim.
+	type MaxOwners: Get<u32>;
+	/// Maximum length in bytes of a claim.
+	type MaxProofLength: Get<u8>;
+	/// Only accept claims that are an encoded `TaggedDigest`.
+	type DigestClaimsOnly: Get<bool>;
+}
+
+/// Hash algorithm of a tagged digest.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum HashAlgo {
+	Sha256,
+	Blake2_256,
+	Keccak256,
+}
+
+/// A document notarised by its digest, encoded into the 33 bytes of the claim.
+pub type TaggedDigest = (HashAlgo, [u8; 32]);
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Proof<AccountId, BlockNumber> {
+	/// Sorted and without duplicates, a claim created with `create_claim` has a single owner.
+	pub owners: Vec<AccountId>,
+	/// Number of owners that have to approve a revocation or a transfer.
+	pub threshold: u32,
+	pub block: BlockNumber,
+}
+
+impl<AccountId: Ord, BlockNumber> Proof<AccountId, BlockNumber> {
+	fn is_owner(&self, who: &AccountId) -> bool {
+		self.owners.binary_search(who).is_ok()
+	}
+}
+
+/// What the owners of a claim approve.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum ClaimAction<AccountId> {
+	Revoke,
+	/// Transfer the claim to a single new owner.
+	Transfer(AccountId),
 }
 
+type ProofOf<T> = Proof<<T as system::Trait>::AccountId, <T as system::Trait>::BlockNumber>;
+
 // This pallet's storage items.
 decl_storage! {
 	trait Store for Module<T: Trait> as TemplateModule {
-		Proofs get(fn proof): map hasher(twox_64_concat) Vec<u8> => (T::AccountId, T::BlockNumber);
+		// Claims are chosen by the sender, they are hashed with a cryptographic hasher.
+		Proofs get(fn proof): map hasher(blake2_128_concat) Vec<u8> => Option<ProofOf<T>>;
+		/// Actions the owners of a shared claim approved, at most a revocation and a transfer per owner.
+		/// Cleared when the claim is revoked or transferred.
+		Approvals get(fn approvals): map hasher(blake2_128_concat) Vec<u8> => Vec<(T::AccountId, ClaimAction<T::AccountId>)>;
+		StorageVersion get(fn storage_version): u32;
 	}
 }
 
@@ -39,7 +101,13 @@ decl_storage! {
 decl_event!(
 	pub enum Event<T> where AccountId = <T as system::Trait>::AccountId {
 		ClaimCreated(AccountId, Vec<u8>),
+		/// A claim is created with several owners. [creator, claim, threshold]
+		SharedClaimCreated(AccountId, Vec<u8>, u32),
 		ClaimRevoked(AccountId, Vec<u8>),
+		/// A claim is transferred. [from, to, claim]
+		ClaimTransferred(AccountId, AccountId, Vec<u8>),
+		/// An owner of a shared claim approved an action below the threshold. [owner, claim, action, approvals]
+		ActionApproved(AccountId, Vec<u8>, ClaimAction<AccountId>, u32),
 	}
 );
 
@@ -47,8 +115,18 @@ decl_event!(
 decl_error! {
 	pub enum Error for Module<T: Trait> {
 		DuplicateClaim,
+		/// The claim is longer than `MaxProofLength`.
+		ProofTooLong,
+		/// Only tagged digests are accepted as claims.
+		NotTaggedDigest,
 		ClaimNotExist,
 		NotClaimOwner,
+		/// A shared claim has more than `MaxOwners` owners.
+		TooManyOwners,
+		/// The threshold is zero or above the number of owners.
+		InvalidThreshold,
+		/// The owner already approved this action.
+		AlreadyApproved,
 	}
 }
 
@@ -61,39 +139,189 @@ decl_module! {
 		// it is needed only if you are using errors in your pallet
 		type Error = Error<T>;
 
+		const MaxOwners: u32 = T::MaxOwners::get();
+		const MaxProofLength: u8 = T::MaxProofLength::get();
+		const DigestClaimsOnly: bool = T::DigestClaimsOnly::get();
+
 		// Initializing events
 		// this is needed only if you are using events in your pallet
 		fn deposit_event() = default;
 
-		#[weight = 0]
+		fn on_runtime_upgrade() -> Weight {
+			let version = Self::storage_version();
+			if version < STORAGE_VERSION {
+				let migrated = Self::migrate_claims(version);
+				StorageVersion::put(STORAGE_VERSION);
+				// Every entry moved is removed and inserted again.
+				return T::DbWeight::get().reads_writes(migrated + 1, 2 * migrated + 1);
+			}
+			T::DbWeight::get().reads(1)
+		}
+
+		#[weight = T::DbWeight::get().reads_writes(1, 1)]
 		pub fn create_claim(origin, claim: Vec<u8>) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			ensure!(!Proofs::<T>::contains_key(&claim), Error::<T>::DuplicateClaim);
-			
-			Proofs::<T>::insert(&claim, (sender.clone(), system::Module::<T>::block_number()));
+			Self::ensure_new_claim(&claim)?;
+
+			Proofs::<T>::insert(&claim, Proof {
+				owners: vec![sender.clone()],
+				threshold: 1,
+				block: system::Module::<T>::block_number(),
+			});
 
 			Self::deposit_event(RawEvent::ClaimCreated(sender, claim));
 
 			Ok(())
 		}
 
-		#[weight = 0]
-		pub fn revoke_claim(origin, claim: Vec<u8>) -> DispatchResult {
+		/// Creates a claim of the digest of a document, under the encoded `TaggedDigest` as claim.
+		#[weight = T::DbWeight::get().reads_writes(1, 1)]
+		pub fn create_digest_claim(origin, algo: HashAlgo, digest: [u8; 32]) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let claim = (algo, digest).encode();
+
+			Self::ensure_new_claim(&claim)?;
+
+			Proofs::<T>::insert(&claim, Proof {
+				owners: vec![sender.clone()],
+				threshold: 1,
+				block: system::Module::<T>::block_number(),
+			});
+
+			Self::deposit_event(RawEvent::ClaimCreated(sender, claim));
+
+			Ok(())
+		}
+
+		/// Creates a claim owned by `owners`, the sender among them. `threshold` of them have to
+		/// approve before it is revoked or transferred.
+		#[weight = T::DbWeight::get().reads_writes(1, 1)]
+		pub fn create_shared_claim(origin, claim: Vec<u8>, owners: Vec<T::AccountId>, threshold: u32) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+			Self::ensure_new_claim(&claim)?;
+
+			let mut owners = owners;
+			owners.sort();
+			owners.dedup();
+			ensure!(owners.len() as u32 <= T::MaxOwners::get(), Error::<T>::TooManyOwners);
+			ensure!(threshold > 0 && threshold as usize <= owners.len(), Error::<T>::InvalidThreshold);
+			let proof = Proof { owners, threshold, block: system::Module::<T>::block_number() };
+			ensure!(proof.is_owner(&sender), Error::<T>::NotClaimOwner);
+
+			Proofs::<T>::insert(&claim, proof);
 
-			let (owner, _) = Proofs::<T>::get(&claim);
+			Self::deposit_event(RawEvent::SharedClaimCreated(sender, claim, threshold));
 
-			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+			Ok(())
+		}
+
+		/// Revokes a claim. An owner of a shared claim approves the revocation, the claim is revoked
+		/// once the threshold is reached.
+		#[weight = T::DbWeight::get().reads_writes(2, 2)]
+		pub fn revoke_claim(origin, claim: Vec<u8>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			if !Self::approve(&sender, &claim, ClaimAction::Revoke)? {
+				return Ok(());
+			}
 
 			Proofs::<T>::remove(&claim);
+			Approvals::<T>::remove(&claim);
 
 			Self::deposit_event(RawEvent::ClaimRevoked(sender, claim));
 
 			Ok(())
 		}
 
+		/// Transfers a claim to `dest`, its only owner from then on. An owner of a shared claim
+		/// approves the transfer, the claim is transferred once the threshold is reached.
+		#[weight = T::DbWeight::get().reads_writes(2, 2)]
+		pub fn transfer_claim(origin, claim: Vec<u8>, dest: T::AccountId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			if !Self::approve(&sender, &claim, ClaimAction::Transfer(dest.clone()))? {
+				return Ok(());
+			}
+
+			Proofs::<T>::mutate(&claim, |proof| if let Some(proof) = proof {
+				proof.owners = vec![dest.clone()];
+				proof.threshold = 1;
+			});
+			Approvals::<T>::remove(&claim);
+
+			Self::deposit_event(RawEvent::ClaimTransferred(sender, dest, claim));
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	fn ensure_new_claim(claim: &[u8]) -> Result<(), Error<T>> {
+		ensure!(claim.len() <= T::MaxProofLength::get() as usize, Error::<T>::ProofTooLong);
+		if T::DigestClaimsOnly::get() {
+			TaggedDigest::decode_all(claim).map_err(|_| Error::<T>::NotTaggedDigest)?;
+		}
+		ensure!(!Proofs::<T>::contains_key(claim), Error::<T>::DuplicateClaim);
+		Ok(())
+	}
+
+	/// Records the approval of `action` on `claim` by `who`, true once enough owners approved it.
+	fn approve(who: &T::AccountId, claim: &[u8], action: ClaimAction<T::AccountId>) -> Result<bool, Error<T>> {
+		let proof = Self::proof(claim).ok_or(Error::<T>::ClaimNotExist)?;
+		ensure!(proof.is_owner(who), Error::<T>::NotClaimOwner);
+		if proof.threshold <= 1 {
+			return Ok(true);
+		}
+
+		let mut approvals = Self::approvals(claim);
+		ensure!(!approvals.iter().any(|(owner, approved)| owner == who && *approved == action), Error::<T>::AlreadyApproved);
+		// Approving a transfer withdraws the owner's approval of a transfer to someone else.
+		approvals.retain(|(owner, approved)| owner != who || mem::discriminant(approved) != mem::discriminant(&action));
+		approvals.push((who.clone(), action.clone()));
+		let count = approvals.iter().filter(|(_, approved)| *approved == action).count() as u32;
+		if count >= proof.threshold {
+			return Ok(true);
+		}
+
+		Approvals::<T>::insert(claim, approvals);
+		Self::deposit_event(RawEvent::ActionApproved(who.clone(), claim.to_vec(), action, count));
+		Ok(false)
+	}
+
+	/// Moves the claims stored under `twox_64_concat` before storage version 2, converting the
+	/// `(owner, block)` proofs of storage version 0. Returns the number of entries moved.
+	fn migrate_claims(version: u32) -> Weight {
+		let mut migrated: Weight = 0;
+		if version == 0 {
+			for (claim, (owner, block)) in Self::drain_twox_claims::<(T::AccountId, T::BlockNumber)>(b"Proofs") {
+				Proofs::<T>::insert(claim, Proof { owners: vec![owner], threshold: 1, block });
+				migrated += 1;
+			}
+		} else {
+			for (claim, proof) in Self::drain_twox_claims::<ProofOf<T>>(b"Proofs") {
+				Proofs::<T>::insert(claim, proof);
+				migrated += 1;
+			}
+			for (claim, approvals) in Self::drain_twox_claims::<Vec<(T::AccountId, ClaimAction<T::AccountId>)>>(b"Approvals") {
+				Approvals::<T>::insert(claim, approvals);
+				migrated += 1;
+			}
+		}
+		migrated
+	}
+
+	/// Removes the entries of a map keyed by `twox_64_concat` claims. They are all taken before
+	/// anything is inserted again under the same prefix.
+	fn drain_twox_claims<V: Decode>(item: &[u8]) -> Vec<(Vec<u8>, V)> {
+		StorageIterator::<V>::new(b"TemplateModule", item).drain()
+			.filter_map(|(key, value)| {
+				// The key is the 8 byte hash followed by the encoded claim.
+				let mut encoded = key.get(8..)?;
+				Vec::<u8>::decode(&mut encoded).ok().map(|claim| (claim, value))
+			})
+			.collect()
 	}
 }
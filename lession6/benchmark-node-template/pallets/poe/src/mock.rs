@@ -2,7 +2,8 @@
 
 use crate::{Module, Trait};
 use sp_core::H256;
-use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
+use frame_support::{impl_outer_origin, parameter_types, weights::Weight, traits::Get};
+use std::cell::RefCell;
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
 };
@@ -21,6 +22,8 @@ parameter_types! {
 	pub const MaximumBlockWeight: Weight = 1024;
 	pub const MaximumBlockLength: u32 = 2 * 1024;
 	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+	pub const MaxOwners: u32 = 3;
+	pub const MaxProofLength: u8 = 40;
 }
 impl frame_system::Trait for Test {
 	type Origin = Origin;
@@ -35,6 +38,10 @@ impl frame_system::Trait for Test {
 	type Event = ();
 	type BlockHashCount = BlockHashCount;
 	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
 	type MaximumBlockLength = MaximumBlockLength;
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type Version = ();
@@ -43,8 +50,28 @@ impl frame_system::Trait for Test {
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 }
+thread_local! {
+	static DIGEST_CLAIMS_ONLY: RefCell<bool> = RefCell::new(false);
+}
+
+/// Off unless a test switches it on.
+pub struct DigestClaimsOnly;
+impl DigestClaimsOnly {
+	pub fn set(value: bool) {
+		DIGEST_CLAIMS_ONLY.with(|v| *v.borrow_mut() = value);
+	}
+}
+impl Get<bool> for DigestClaimsOnly {
+	fn get() -> bool {
+		DIGEST_CLAIMS_ONLY.with(|v| *v.borrow())
+	}
+}
+
 impl Trait for Test {
 	type Event = ();
+	type MaxOwners = MaxOwners;
+	type MaxProofLength = MaxProofLength;
+	type DigestClaimsOnly = DigestClaimsOnly;
 }
 pub type TemplateModule = Module<Test>;
 
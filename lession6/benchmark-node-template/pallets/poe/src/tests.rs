@@ -1,5 +1,131 @@
 // Tests to be written here
 
 use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
+use frame_support::{
+	assert_ok, assert_noop, Twox64Concat, StorageHasher,
+	storage::{migration, IterableStorageMap}, traits::OnRuntimeUpgrade,
+};
+use super::*;
 
+fn proof(owners: Vec<u64>, threshold: u32) -> Option<Proof<u64, u64>> {
+	Some(Proof { owners, threshold, block: frame_system::Module::<Test>::block_number() })
+}
+
+#[test]
+fn create_and_revoke_claim() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim), proof(vec![1], 1));
+		assert_noop!(TemplateModule::create_claim(Origin::signed(2), claim.clone()), Error::<Test>::DuplicateClaim);
+
+		assert_noop!(TemplateModule::revoke_claim(Origin::signed(2), claim.clone()), Error::<Test>::NotClaimOwner);
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim), None);
+		assert_noop!(TemplateModule::revoke_claim(Origin::signed(1), claim.clone()), Error::<Test>::ClaimNotExist);
+	});
+}
+
+#[test]
+fn transfer_claim_works() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), claim.clone()));
+
+		assert_noop!(TemplateModule::transfer_claim(Origin::signed(2), claim.clone(), 3), Error::<Test>::NotClaimOwner);
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), claim.clone(), 2));
+		assert_eq!(Proofs::<Test>::get(&claim), proof(vec![2], 1));
+	});
+}
+
+#[test]
+fn shared_claim_needs_threshold() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_noop!(
+			TemplateModule::create_shared_claim(Origin::signed(1), claim.clone(), vec![1, 2, 3, 4], 2),
+			Error::<Test>::TooManyOwners
+		);
+		assert_noop!(
+			TemplateModule::create_shared_claim(Origin::signed(1), claim.clone(), vec![1, 2], 3),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_ok!(TemplateModule::create_shared_claim(Origin::signed(1), claim.clone(), vec![3, 1, 2, 1], 2));
+		assert_eq!(Proofs::<Test>::get(&claim), proof(vec![1, 2, 3], 2));
+
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(2), claim.clone()));
+		assert_noop!(TemplateModule::revoke_claim(Origin::signed(2), claim.clone()), Error::<Test>::AlreadyApproved);
+		// An owner approves one transfer at a time.
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), claim.clone(), 4));
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(1), claim.clone(), 5));
+		assert_ok!(TemplateModule::transfer_claim(Origin::signed(3), claim.clone(), 4));
+		assert_eq!(Proofs::<Test>::get(&claim), proof(vec![1, 2, 3], 2));
+
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(3), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim), None);
+		assert!(Approvals::<Test>::get(&claim).is_empty());
+	});
+}
+
+/// Puts `value` under `claim` in `item` the way storage version 0 and 1 did, keyed by `twox_64_concat`.
+fn put_twox<V: Encode>(item: &[u8], claim: &[u8], value: V) {
+	let key = claim.to_vec().encode();
+	migration::put_storage_value(b"TemplateModule", item, &Twox64Concat::hash(&key), value);
+}
+
+#[test]
+fn migration_converts_old_proofs() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		put_twox(b"Proofs", &claim, (1u64, 5u64));
+
+		assert_eq!(TemplateModule::storage_version(), 0);
+		<TemplateModule as OnRuntimeUpgrade>::on_runtime_upgrade();
+
+		assert_eq!(TemplateModule::storage_version(), STORAGE_VERSION);
+		assert_eq!(Proofs::<Test>::get(&claim), Some(Proof { owners: vec![1], threshold: 1, block: 5 }));
+		assert_eq!(Proofs::<Test>::iter().count(), 1);
+	});
+}
+
+#[test]
+fn migration_rehashes_claims() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		let old = Proof { owners: vec![1, 2], threshold: 2, block: 5 };
+		put_twox(b"Proofs", &claim, old.clone());
+		put_twox(b"Approvals", &claim, vec![(2u64, ClaimAction::<u64>::Revoke)]);
+		StorageVersion::put(1);
+
+		<TemplateModule as OnRuntimeUpgrade>::on_runtime_upgrade();
+
+		assert_eq!(Proofs::<Test>::get(&claim), Some(old));
+		assert_eq!(Approvals::<Test>::get(&claim), vec![(2, ClaimAction::Revoke)]);
+		assert_ok!(TemplateModule::revoke_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim), None);
+	});
+}
+
+#[test]
+fn create_claim_failed_when_claim_too_long() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(TemplateModule::create_claim(Origin::signed(1), vec![0; 41]), Error::<Test>::ProofTooLong);
+		assert_ok!(TemplateModule::create_claim(Origin::signed(1), vec![0; 40]));
+	});
+}
+
+#[test]
+fn digest_claims_are_normalised() {
+	new_test_ext().execute_with(|| {
+		DigestClaimsOnly::set(true);
+		let digest = [7; 32];
+		assert_noop!(TemplateModule::create_claim(Origin::signed(1), digest.to_vec()), Error::<Test>::NotTaggedDigest);
+		assert_ok!(TemplateModule::create_digest_claim(Origin::signed(1), HashAlgo::Sha256, digest));
+		assert_noop!(
+			TemplateModule::create_claim(Origin::signed(2), (HashAlgo::Sha256, digest).encode()),
+			Error::<Test>::DuplicateClaim
+		);
+		assert_ok!(TemplateModule::create_digest_claim(Origin::signed(2), HashAlgo::Keccak256, digest));
+		DigestClaimsOnly::set(false);
+	});
+}
@@ -267,8 +267,17 @@ impl coinflip::Trait for Runtime {
 	type Event = Event;
 }
 
+parameter_types! {
+	pub const MaxClaimOwners: u32 = 16;
+	pub const MaxProofLength: u8 = 128;
+	pub const DigestClaimsOnly: bool = false;
+}
+
 impl poe::Trait for Runtime {
 	type Event = Event;
+	type MaxOwners = MaxClaimOwners;
+	type MaxProofLength = MaxProofLength;
+	type DigestClaimsOnly = DigestClaimsOnly;
 }
 
 impl<C> system::offchain::SendTransactionTypes<C> for Runtime where
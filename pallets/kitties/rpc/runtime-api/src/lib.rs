@@ -0,0 +1,26 @@
+//! Runtime API definition of the kitties pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+pub use pallet_kitties::{KittiesPage, KittyInfo, KittyMetadata, MAX_PAGE_SIZE, genetics::KittyTraits};
+
+sp_api::decl_runtime_apis! {
+	pub trait KittiesApi<AccountId, KittyIndex, Balance> where
+		AccountId: Codec,
+		KittyIndex: Codec,
+		Balance: Codec,
+	{
+		/// Decoded traits of a kitty, `None` if it does not exist.
+		fn kitty_traits(kitty_id: KittyIndex) -> Option<KittyTraits>;
+		/// Number of kitties ever created.
+		fn kitties_count() -> KittyIndex;
+		/// Number of kitties owned by an account.
+		fn owned_kitties_count(account: AccountId) -> u64;
+		/// A page of at most `limit` kitties of `account` starting at `cursor`, capped by `MAX_PAGE_SIZE`.
+		fn owned_kitties(account: AccountId, cursor: Option<KittyIndex>, limit: u32) -> KittiesPage<AccountId, KittyIndex, Balance>;
+		/// Name, description and art hash of a kitty, `None` if none is set.
+		fn kitty_metadata(kitty_id: KittyIndex) -> Option<KittyMetadata>;
+	}
+}
@@ -0,0 +1,95 @@
+//! RPC interface of the kitties pallet.
+
+use std::sync::Arc;
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_kitties_rpc_runtime_api::{KittiesApi as KittiesRuntimeApi, KittiesPage, KittyInfo, KittyMetadata};
+
+/// Kitties RPC methods.
+#[rpc]
+pub trait KittiesApi<BlockHash, AccountId, KittyIndex, Balance> {
+	/// Number of kitties ever created.
+	#[rpc(name = "kitties_kittiesCount")]
+	fn kitties_count(&self, at: Option<BlockHash>) -> Result<KittyIndex>;
+
+	/// Number of kitties owned by an account.
+	#[rpc(name = "kitties_ownedKittiesCount")]
+	fn owned_kitties_count(&self, account: AccountId, at: Option<BlockHash>) -> Result<u64>;
+
+	/// A page of the kitties owned by an account. Pass the `next` cursor of a page to get the following one.
+	#[rpc(name = "kitties_ownedKitties")]
+	fn owned_kitties(
+		&self,
+		account: AccountId,
+		cursor: Option<KittyIndex>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> Result<KittiesPage<AccountId, KittyIndex, Balance>>;
+
+	/// Name, description and art hash of a kitty.
+	#[rpc(name = "kitties_kittyMetadata")]
+	fn kitty_metadata(&self, kitty_id: KittyIndex, at: Option<BlockHash>) -> Result<Option<KittyMetadata>>;
+}
+
+/// Kitties RPC methods backed by the runtime API.
+pub struct Kitties<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Kitties<C, B> {
+	/// Kitties RPC over the given client.
+	pub fn new(client: Arc<C>) -> Self {
+		Kitties { client, _marker: Default::default() }
+	}
+}
+
+fn runtime_error<E: std::fmt::Debug>(err: E) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(1),
+		message: "Unable to query kitties".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+impl<C, Block, AccountId, KittyIndex, Balance> KittiesApi<<Block as BlockT>::Hash, AccountId, KittyIndex, Balance>
+	for Kitties<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: KittiesRuntimeApi<Block, AccountId, KittyIndex, Balance>,
+	AccountId: Codec,
+	KittyIndex: Codec,
+	Balance: Codec,
+{
+	fn kitties_count(&self, at: Option<<Block as BlockT>::Hash>) -> Result<KittyIndex> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().kitties_count(&at).map_err(runtime_error)
+	}
+
+	fn owned_kitties_count(&self, account: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<u64> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().owned_kitties_count(&at, account).map_err(runtime_error)
+	}
+
+	fn owned_kitties(
+		&self,
+		account: AccountId,
+		cursor: Option<KittyIndex>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<KittiesPage<AccountId, KittyIndex, Balance>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().owned_kitties(&at, account, cursor, limit).map_err(runtime_error)
+	}
+
+	fn kitty_metadata(&self, kitty_id: KittyIndex, at: Option<<Block as BlockT>::Hash>) -> Result<Option<KittyMetadata>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		self.client.runtime_api().kitty_metadata(&at, kitty_id).map_err(runtime_error)
+	}
+}
@@ -0,0 +1,497 @@
+//! Benchmarks of the kitties pallet.
+
+use super::*;
+use frame_benchmarking::{benchmarks, account, whitelisted_caller};
+use frame_support::traits::OnInitialize;
+use frame_system::RawOrigin;
+use sp_std::{prelude::*, vec};
+
+/// Upper end of the kitties owned by the accounts of a benchmark before the call. Linked list
+/// operations should not depend on the length of the list, the component `n` checks that.
+const MAX_OWNED: u32 = 1_000;
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who = account(name, index, 0);
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+	who
+}
+
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller = whitelisted_caller();
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+	caller
+}
+
+/// The open collection the benchmarks mint in, created on first use.
+fn collection<T: Config>() -> T::CollectionId {
+	let collection_id = T::CollectionId::zero();
+	if !Collections::<T>::contains_key(collection_id) {
+		Collections::<T>::insert(collection_id, Collection {
+			owner: funded_account::<T>("curator", 0),
+			max_supply: None,
+			supply: 0,
+			mint_price: Zero::zero(),
+			allowlist_only: false,
+			cross_breeding: false,
+		});
+		NextCollectionId::<T>::put(collection_id + One::one());
+	}
+	collection_id
+}
+
+/// Mints `n` kitties to `owner` and returns the id of the last one.
+fn mint<T: Config>(owner: &T::AccountId, n: u32) -> T::KittyIndex {
+	let collection_id = collection::<T>();
+	for _ in 0..n {
+		Module::<T>::create(RawOrigin::Signed(owner.clone()).into(), collection_id).expect("owner can mint");
+	}
+	Module::<T>::kitties_count() - One::one()
+}
+
+/// Mints a kitty to a breeder that takes the highest royalty and hands it to `owner`, so selling
+/// it pays the royalty.
+fn resold<T: Config>(owner: &T::AccountId) -> T::KittyIndex {
+	let breeder = funded_account::<T>("breeder", 0);
+	let kitty_id = mint::<T>(&breeder, 1);
+	Module::<T>::set_royalty(RawOrigin::Signed(breeder.clone()).into(), kitty_id, T::MaxRoyalty::get()).expect("breeder sets the royalty");
+	Module::<T>::transfer(RawOrigin::Signed(breeder).into(), owner.clone(), kitty_id).expect("breeder owns the kitty");
+	kitty_id
+}
+
+/// Sets the longest metadata on a kitty of `owner`.
+fn set_full_metadata<T: Config>(owner: &T::AccountId, kitty_id: T::KittyIndex) {
+	Module::<T>::set_metadata(
+		RawOrigin::Signed(owner.clone()).into(),
+		kitty_id,
+		vec![b'n'; T::MaxNameLength::get() as usize],
+		vec![b'd'; T::MaxDescriptionLength::get() as usize],
+		Some([0u8; 32]),
+	).expect("owner can set the metadata");
+}
+
+/// A collection created by `owner` through the call, as opposed to the open one of `collection`.
+fn new_collection<T: Config>(owner: &T::AccountId) -> T::CollectionId {
+	let collection_id = Module::<T>::next_collection_id();
+	Module::<T>::create_collection(RawOrigin::Signed(owner.clone()).into(), None, Zero::zero(), false, false)
+		.expect("owner can pay the deposit");
+	collection_id
+}
+
+/// Worst case minting: a deposit to reserve and no limit check short cut.
+fn set_up_minting<T: Config>(owned: u32) {
+	MintDeposit::<T>::put(BalanceOf::<T>::from(1_000u32));
+	MaxKittiesPerAccount::put(owned * 2 + 3);
+}
+
+/// Worst case collection: `minter` is checked against the allowlist and the supply cap, and pays
+/// the mint price.
+fn gate_collection<T: Config>(minter: &T::AccountId) {
+	let collection_id = collection::<T>();
+	Collections::<T>::mutate(collection_id, |collection| if let Some(collection) = collection {
+		collection.max_supply = Some(u32::max_value());
+		collection.mint_price = 100u32.into();
+		collection.allowlist_only = true;
+	});
+	CollectionAllowlist::<T>::insert(collection_id, minter, true);
+}
+
+benchmarks! {
+	create {
+		let n in 0 .. MAX_OWNED;
+		set_up_minting::<T>(n);
+		let caller = funded_caller::<T>();
+		mint::<T>(&caller, n);
+		gate_collection::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), collection::<T>())
+	verify {
+		assert_eq!(Module::<T>::owned_kitties_count(&caller), n + 1);
+	}
+
+	breed {
+		let n in 0 .. MAX_OWNED;
+		set_up_minting::<T>(n);
+		// Every allele mutates, both parents have a cooldown.
+		MutationRate::put(Permill::one());
+		BreedingCooldown::<T>::put(T::BlockNumber::from(10u32));
+		let caller = funded_caller::<T>();
+		let kitty_id_1 = mint::<T>(&caller, 1);
+		mint::<T>(&caller, n);
+		let kitty_id_2 = mint::<T>(&caller, 1);
+		gate_collection::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id_1, kitty_id_2)
+	verify {
+		assert_eq!(Module::<T>::owned_kitties_count(&caller), n + 3);
+		assert!(Module::<T>::kitty_cooldown(kitty_id_1) > frame_system::Module::<T>::block_number());
+	}
+
+	transfer {
+		let n in 0 .. MAX_OWNED;
+		let caller = funded_caller::<T>();
+		let to = funded_account::<T>("to", 0);
+		mint::<T>(&caller, n);
+		let kitty_id = mint::<T>(&caller, 1);
+		mint::<T>(&caller, n);
+		mint::<T>(&to, n);
+		// Transferring ends the sire listing.
+		Module::<T>::list_sire(RawOrigin::Signed(caller.clone()).into(), kitty_id, 1u32.into(), T::BlockNumber::max_value())?;
+	}: _(RawOrigin::Signed(caller), to.clone(), kitty_id)
+	verify {
+		assert_eq!(Module::<T>::kitty_owner(kitty_id), Some(to));
+	}
+
+	ask {
+		let caller = funded_caller::<T>();
+		let kitty_id = mint::<T>(&caller, 1);
+	}: _(RawOrigin::Signed(caller), kitty_id, Some(100u32.into()))
+	verify {
+		assert_eq!(Module::<T>::kitty_price(kitty_id), Some(100u32.into()));
+	}
+
+	buy {
+		let n in 0 .. MAX_OWNED;
+		let seller = funded_account::<T>("seller", 0);
+		let caller = funded_caller::<T>();
+		mint::<T>(&seller, n);
+		let kitty_id = resold::<T>(&seller);
+		mint::<T>(&seller, n);
+		mint::<T>(&caller, n);
+		Module::<T>::ask(RawOrigin::Signed(seller).into(), kitty_id, Some(100u32.into()))?;
+		// Every share of the price is paid out.
+		MarketplaceFee::put(Permill::from_percent(1));
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, PriceLimit::Max(100u32.into()), 1)
+	verify {
+		assert_eq!(Module::<T>::kitty_owner(kitty_id), Some(caller));
+	}
+
+	gift {
+		let m in 0 .. T::MaxMemoLength::get();
+		let caller = funded_caller::<T>();
+		let to = funded_account::<T>("to", 0);
+		let kitty_id = mint::<T>(&caller, 1);
+	}: _(RawOrigin::Signed(caller), to.clone(), kitty_id, vec![0u8; m as usize])
+	verify {
+		assert_eq!(Module::<T>::kitty_owner(kitty_id), Some(to));
+	}
+
+	burn {
+		let o in 0 .. T::MaxOffersPerKitty::get();
+		set_up_minting::<T>(0);
+		let caller = funded_caller::<T>();
+		let kitty_id = mint::<T>(&caller, 1);
+		set_full_metadata::<T>(&caller, kitty_id);
+		// Every offer is refunded and taken off the sweep of its own block.
+		let now = frame_system::Module::<T>::block_number();
+		for i in 0 .. o {
+			let buyer = funded_account::<T>("buyer", i);
+			Module::<T>::make_offer(RawOrigin::Signed(buyer).into(), kitty_id, 10u32.into(), now + (i + 1).into())?;
+		}
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert_eq!(Module::<T>::kitty_owner(kitty_id), None);
+	}
+
+	create_auction {
+		let caller = funded_caller::<T>();
+		let kitty_id = mint::<T>(&caller, 1);
+		Module::<T>::ask(RawOrigin::Signed(caller.clone()).into(), kitty_id, Some(100u32.into()))?;
+		let end = frame_system::Module::<T>::block_number() + 10u32.into();
+	}: _(RawOrigin::Signed(caller), kitty_id, AuctionKind::Dutch, 100u32.into(), 10u32.into(), end)
+	verify {
+		assert!(Module::<T>::auction(kitty_id).is_some());
+	}
+
+	bid {
+		let seller = funded_account::<T>("seller", 0);
+		let kitty_id = mint::<T>(&seller, 1);
+		let end = frame_system::Module::<T>::block_number() + 10u32.into();
+		Module::<T>::create_auction(RawOrigin::Signed(seller).into(), kitty_id, AuctionKind::English, 10u32.into(), 10u32.into(), end)?;
+		// The outbid funds are released.
+		let bidder = funded_account::<T>("bidder", 0);
+		Module::<T>::bid(RawOrigin::Signed(bidder).into(), kitty_id, 10u32.into())?;
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, 20u32.into())
+	verify {
+		assert_eq!(Module::<T>::auction(kitty_id).and_then(|auction| auction.bid), Some((caller, 20u32.into())));
+	}
+
+	cancel_auction {
+		let caller = funded_caller::<T>();
+		let kitty_id = mint::<T>(&caller, 1);
+		let end = frame_system::Module::<T>::block_number() + 10u32.into();
+		Module::<T>::create_auction(RawOrigin::Signed(caller.clone()).into(), kitty_id, AuctionKind::English, 10u32.into(), 10u32.into(), end)?;
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert!(Module::<T>::auction(kitty_id).is_none());
+	}
+
+	make_offer {
+		let owner = funded_account::<T>("owner", 0);
+		let kitty_id = mint::<T>(&owner, 1);
+		let caller = funded_caller::<T>();
+		// Replacing an offer with another expiry moves it to the sweep of the new block.
+		let now = frame_system::Module::<T>::block_number();
+		Module::<T>::make_offer(RawOrigin::Signed(caller.clone()).into(), kitty_id, 10u32.into(), now + 10u32.into())?;
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, 20u32.into(), now + 20u32.into())
+	verify {
+		assert_eq!(Module::<T>::offer((kitty_id, caller)).map(|offer| offer.amount), Some(20u32.into()));
+	}
+
+	withdraw_offer {
+		let owner = funded_account::<T>("owner", 0);
+		let kitty_id = mint::<T>(&owner, 1);
+		let caller = funded_caller::<T>();
+		let expiry = frame_system::Module::<T>::block_number() + 10u32.into();
+		Module::<T>::make_offer(RawOrigin::Signed(caller.clone()).into(), kitty_id, 10u32.into(), expiry)?;
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id)
+	verify {
+		assert!(Module::<T>::offer((kitty_id, caller)).is_none());
+	}
+
+	accept_offer {
+		let caller = funded_caller::<T>();
+		let kitty_id = resold::<T>(&caller);
+		let buyer = funded_account::<T>("buyer", 0);
+		let expiry = frame_system::Module::<T>::block_number() + 10u32.into();
+		Module::<T>::make_offer(RawOrigin::Signed(buyer.clone()).into(), kitty_id, 100u32.into(), expiry)?;
+	}: _(RawOrigin::Signed(caller), kitty_id, buyer.clone())
+	verify {
+		assert_eq!(Module::<T>::kitty_owner(kitty_id), Some(buyer));
+	}
+
+	list_sire {
+		let caller = funded_caller::<T>();
+		let kitty_id = mint::<T>(&caller, 1);
+	}: _(RawOrigin::Signed(caller), kitty_id, 10u32.into(), T::BlockNumber::max_value())
+	verify {
+		assert!(Module::<T>::sire_listing(kitty_id).is_some());
+	}
+
+	cancel_sire {
+		let caller = funded_caller::<T>();
+		let kitty_id = mint::<T>(&caller, 1);
+		Module::<T>::list_sire(RawOrigin::Signed(caller.clone()).into(), kitty_id, 10u32.into(), T::BlockNumber::max_value())?;
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert!(Module::<T>::sire_listing(kitty_id).is_none());
+	}
+
+	breed_with_sire {
+		set_up_minting::<T>(0);
+		MutationRate::put(Permill::one());
+		BreedingCooldown::<T>::put(T::BlockNumber::from(10u32));
+		let sire_owner = funded_account::<T>("sire_owner", 0);
+		let sire_id = mint::<T>(&sire_owner, 1);
+		Module::<T>::list_sire(RawOrigin::Signed(sire_owner).into(), sire_id, 10u32.into(), T::BlockNumber::max_value())?;
+		let caller = funded_caller::<T>();
+		let kitty_id = mint::<T>(&caller, 1);
+		gate_collection::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, sire_id, 10u32.into())
+	verify {
+		assert_eq!(Module::<T>::owned_kitties_count(&caller), 2);
+	}
+
+	approve {
+		// The sender approves as an operator of the owner.
+		let owner = funded_account::<T>("owner", 0);
+		let kitty_id = mint::<T>(&owner, 1);
+		let caller = funded_caller::<T>();
+		Module::<T>::set_approval_for_all(RawOrigin::Signed(owner).into(), caller.clone(), true)?;
+		let spender: T::AccountId = account("spender", 0, 0);
+	}: _(RawOrigin::Signed(caller), Some(spender.clone()), kitty_id)
+	verify {
+		assert_eq!(Module::<T>::kitty_approval(kitty_id), Some(spender));
+	}
+
+	set_approval_for_all {
+		let caller = funded_caller::<T>();
+		let operator: T::AccountId = account("operator", 0, 0);
+	}: _(RawOrigin::Signed(caller.clone()), operator.clone(), true)
+	verify {
+		assert!(Module::<T>::is_approved_for_all((caller, operator)));
+	}
+
+	transfer_from {
+		// The sender is an operator rather than approved for the kitty, the last check made.
+		let owner = funded_account::<T>("owner", 0);
+		let kitty_id = mint::<T>(&owner, 1);
+		let caller = funded_caller::<T>();
+		Module::<T>::set_approval_for_all(RawOrigin::Signed(owner.clone()).into(), caller.clone(), true)?;
+		let to = funded_account::<T>("to", 0);
+	}: _(RawOrigin::Signed(caller), owner, to.clone(), kitty_id)
+	verify {
+		assert_eq!(Module::<T>::kitty_owner(kitty_id), Some(to));
+	}
+
+	set_royalty {
+		let caller = funded_caller::<T>();
+		let kitty_id = mint::<T>(&caller, 1);
+	}: _(RawOrigin::Signed(caller), kitty_id, T::MaxRoyalty::get())
+	verify {
+		assert_eq!(Module::<T>::kitty_royalty(kitty_id), T::MaxRoyalty::get());
+	}
+
+	offer_loan {
+		let caller = funded_caller::<T>();
+		let kitty_id = mint::<T>(&caller, 1);
+	}: _(RawOrigin::Signed(caller), kitty_id, 10u32.into(), 10u32.into())
+	verify {
+		assert!(Module::<T>::loan_offer(kitty_id).is_some());
+	}
+
+	cancel_loan_offer {
+		let caller = funded_caller::<T>();
+		let kitty_id = mint::<T>(&caller, 1);
+		Module::<T>::offer_loan(RawOrigin::Signed(caller.clone()).into(), kitty_id, 10u32.into(), 10u32.into())?;
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert!(Module::<T>::loan_offer(kitty_id).is_none());
+	}
+
+	borrow {
+		let owner = funded_account::<T>("owner", 0);
+		let kitty_id = mint::<T>(&owner, 1);
+		// Lending ends the sale and the sire listing.
+		Module::<T>::ask(RawOrigin::Signed(owner.clone()).into(), kitty_id, Some(100u32.into()))?;
+		Module::<T>::list_sire(RawOrigin::Signed(owner.clone()).into(), kitty_id, 10u32.into(), T::BlockNumber::max_value())?;
+		Module::<T>::offer_loan(RawOrigin::Signed(owner).into(), kitty_id, 10u32.into(), 10u32.into())?;
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, 10u32.into())
+	verify {
+		assert_eq!(Module::<T>::loan(kitty_id).map(|loan| loan.borrower), Some(caller));
+	}
+
+	set_metadata {
+		// The deposit of the previous metadata goes back to another account.
+		let previous = funded_account::<T>("previous", 0);
+		let caller = funded_caller::<T>();
+		let kitty_id = mint::<T>(&previous, 1);
+		set_full_metadata::<T>(&previous, kitty_id);
+		Module::<T>::transfer(RawOrigin::Signed(previous).into(), caller.clone(), kitty_id)?;
+		let name = vec![b'n'; T::MaxNameLength::get() as usize];
+		let description = vec![b'd'; T::MaxDescriptionLength::get() as usize];
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id, name, description, Some([1u8; 32]))
+	verify {
+		assert_eq!(Module::<T>::metadata_deposit(kitty_id).map(|(depositor, _)| depositor), Some(caller));
+	}
+
+	clear_metadata {
+		let caller = funded_caller::<T>();
+		let kitty_id = mint::<T>(&caller, 1);
+		set_full_metadata::<T>(&caller, kitty_id);
+	}: _(RawOrigin::Signed(caller), kitty_id)
+	verify {
+		assert!(Module::<T>::metadata(kitty_id).is_none());
+	}
+
+	commit_breed {
+		set_up_minting::<T>(0);
+		let caller = funded_caller::<T>();
+		let kitty_id_1 = mint::<T>(&caller, 1);
+		let kitty_id_2 = mint::<T>(&caller, 1);
+		gate_collection::<T>(&caller);
+		// Committing ends the sale of a parent.
+		Module::<T>::ask(RawOrigin::Signed(caller.clone()).into(), kitty_id_1, Some(100u32.into()))?;
+		let commitment = T::Hashing::hash_of(&(&caller, [7u8; 32]));
+	}: _(RawOrigin::Signed(caller.clone()), kitty_id_1, kitty_id_2, commitment)
+	verify {
+		assert!(Module::<T>::breed_commit((caller, commitment)).is_some());
+	}
+
+	reveal_breed {
+		set_up_minting::<T>(0);
+		MutationRate::put(Permill::one());
+		BreedingCooldown::<T>::put(T::BlockNumber::from(10u32));
+		let caller = funded_caller::<T>();
+		let kitty_id_1 = mint::<T>(&caller, 1);
+		let kitty_id_2 = mint::<T>(&caller, 1);
+		gate_collection::<T>(&caller);
+		let secret = [7u8; 32];
+		let commitment = T::Hashing::hash_of(&(&caller, secret));
+		Module::<T>::commit_breed(RawOrigin::Signed(caller.clone()).into(), kitty_id_1, kitty_id_2, commitment)?;
+		// The seed is drawn at the start of the next block.
+		let next = frame_system::Module::<T>::block_number() + One::one();
+		frame_system::Module::<T>::set_block_number(next);
+		Module::<T>::on_initialize(next);
+	}: _(RawOrigin::Signed(caller.clone()), secret)
+	verify {
+		assert_eq!(Module::<T>::owned_kitties_count(&caller), 3);
+	}
+
+	create_collection {
+		let caller = funded_caller::<T>();
+		let collection_id = Module::<T>::next_collection_id();
+	}: _(RawOrigin::Signed(caller), Some(100), 10u32.into(), true, true)
+	verify {
+		assert!(Module::<T>::collection(collection_id).is_some());
+	}
+
+	update_collection {
+		let caller = funded_caller::<T>();
+		let collection_id = new_collection::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), collection_id, 10u32.into(), true, true)
+	verify {
+		assert_eq!(Module::<T>::collection(collection_id).map(|collection| collection.allowlist_only), Some(true));
+	}
+
+	set_allowlist {
+		let n in 1 .. T::MaxBatchSize::get();
+		let caller = funded_caller::<T>();
+		let collection_id = new_collection::<T>(&caller);
+		let accounts: Vec<T::AccountId> = (0 .. n).map(|i| account("member", i, 0)).collect();
+	}: _(RawOrigin::Signed(caller), collection_id, accounts, true)
+	verify {
+		assert_eq!(Module::<T>::collection_allowlist_size(collection_id), n);
+	}
+
+	destroy_collection {
+		let caller = funded_caller::<T>();
+		let collection_id = new_collection::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), collection_id)
+	verify {
+		assert!(Module::<T>::collection(collection_id).is_none());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create::<Test>());
+			assert_ok!(test_benchmark_breed::<Test>());
+			assert_ok!(test_benchmark_transfer::<Test>());
+			assert_ok!(test_benchmark_ask::<Test>());
+			assert_ok!(test_benchmark_buy::<Test>());
+			assert_ok!(test_benchmark_gift::<Test>());
+			assert_ok!(test_benchmark_burn::<Test>());
+			assert_ok!(test_benchmark_create_auction::<Test>());
+			assert_ok!(test_benchmark_bid::<Test>());
+			assert_ok!(test_benchmark_cancel_auction::<Test>());
+			assert_ok!(test_benchmark_make_offer::<Test>());
+			assert_ok!(test_benchmark_withdraw_offer::<Test>());
+			assert_ok!(test_benchmark_accept_offer::<Test>());
+			assert_ok!(test_benchmark_list_sire::<Test>());
+			assert_ok!(test_benchmark_cancel_sire::<Test>());
+			assert_ok!(test_benchmark_breed_with_sire::<Test>());
+			assert_ok!(test_benchmark_approve::<Test>());
+			assert_ok!(test_benchmark_set_approval_for_all::<Test>());
+			assert_ok!(test_benchmark_transfer_from::<Test>());
+			assert_ok!(test_benchmark_set_royalty::<Test>());
+			assert_ok!(test_benchmark_offer_loan::<Test>());
+			assert_ok!(test_benchmark_cancel_loan_offer::<Test>());
+			assert_ok!(test_benchmark_borrow::<Test>());
+			assert_ok!(test_benchmark_set_metadata::<Test>());
+			assert_ok!(test_benchmark_clear_metadata::<Test>());
+			assert_ok!(test_benchmark_commit_breed::<Test>());
+			assert_ok!(test_benchmark_reveal_breed::<Test>());
+			assert_ok!(test_benchmark_create_collection::<Test>());
+			assert_ok!(test_benchmark_update_collection::<Test>());
+			assert_ok!(test_benchmark_set_allowlist::<Test>());
+			assert_ok!(test_benchmark_destroy_collection::<Test>());
+		});
+	}
+}
@@ -0,0 +1,197 @@
+//! The kitty genome.
+//!
+//! The 16 bytes of kitty DNA hold two alleles for each of eight trait loci, the
+//! alleles of locus `n` are the bytes `2 * n` and `2 * n + 1`. The high bit of an
+//! allele marks it as dominant, the remaining bits select one of the variants of
+//! its locus.
+
+use codec::{Encode, Decode};
+use sp_runtime::{Permill, RuntimeDebug};
+
+/// Number of trait loci in a genome.
+pub const LOCI: usize = 8;
+
+const DOMINANT: u8 = 0b1000_0000;
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Locus {
+	Body,
+	Fur,
+	FurColor,
+	Pattern,
+	PatternColor,
+	Eyes,
+	EyeColor,
+	Mouth,
+}
+
+impl Locus {
+	pub const ALL: [Locus; LOCI] = [
+		Locus::Body,
+		Locus::Fur,
+		Locus::FurColor,
+		Locus::Pattern,
+		Locus::PatternColor,
+		Locus::Eyes,
+		Locus::EyeColor,
+		Locus::Mouth,
+	];
+
+	/// Number of distinct variants of this locus.
+	pub fn variants(self) -> u8 {
+		match self {
+			Locus::Body => 4,
+			Locus::Fur => 4,
+			Locus::FurColor => 16,
+			Locus::Pattern => 8,
+			Locus::PatternColor => 16,
+			Locus::Eyes => 8,
+			Locus::EyeColor => 16,
+			Locus::Mouth => 8,
+		}
+	}
+}
+
+/// An expressed trait.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct Gene {
+	pub variant: u8,
+	/// Recessive traits are only expressed when both alleles are recessive, which makes them rarer.
+	pub dominant: bool,
+}
+
+/// The decoded traits of a kitty.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct KittyTraits {
+	pub body: Gene,
+	pub fur: Gene,
+	pub fur_color: Gene,
+	pub pattern: Gene,
+	pub pattern_color: Gene,
+	pub eyes: Gene,
+	pub eye_color: Gene,
+	pub mouth: Gene,
+}
+
+fn gene(locus: Locus, allele: u8) -> Gene {
+	Gene {
+		variant: (allele & !DOMINANT) % locus.variants(),
+		dominant: allele & DOMINANT != 0,
+	}
+}
+
+/// The expressed gene of a locus. A dominant allele wins over a recessive one,
+/// otherwise the lower variant is expressed so the outcome does not depend on
+/// the order of the alleles.
+pub fn express(locus: Locus, dna: &[u8; 16]) -> Gene {
+	let index = locus as usize * 2;
+	let a = gene(locus, dna[index]);
+	let b = gene(locus, dna[index + 1]);
+	match (a.dominant, b.dominant) {
+		(true, false) => a,
+		(false, true) => b,
+		_ => if a.variant <= b.variant { a } else { b },
+	}
+}
+
+pub fn decode(dna: &[u8; 16]) -> KittyTraits {
+	KittyTraits {
+		body: express(Locus::Body, dna),
+		fur: express(Locus::Fur, dna),
+		fur_color: express(Locus::FurColor, dna),
+		pattern: express(Locus::Pattern, dna),
+		pattern_color: express(Locus::PatternColor, dna),
+		eyes: express(Locus::Eyes, dna),
+		eye_color: express(Locus::EyeColor, dna),
+		mouth: express(Locus::Mouth, dna),
+	}
+}
+
+/// The DNA of a child. Each parent passes one of its two alleles of every
+/// locus, picked by `selector`. An allele of the child is replaced by a random
+/// one with a probability of `mutation_rate`, rolled from `mutation`.
+pub fn inherit(dna1: &[u8; 16], dna2: &[u8; 16], selector: &[u8; 16], mutation: &[u8; 16], mutation_rate: Permill) -> [u8; 16] {
+	let threshold = mutation_rate * 0x1_0000u64;
+
+	let mut child = [0u8; 16];
+	for locus in 0..LOCI {
+		let index = locus * 2;
+		let select = selector[index];
+
+		child[index] = dna1[index + (select & 1) as usize];
+		child[index + 1] = dna2[index + ((select >> 1) & 1) as usize];
+
+		let roll = u64::from(mutation[index]) << 8 | u64::from(mutation[index + 1]);
+		if roll < threshold {
+			child[index + ((select >> 2) & 1) as usize] = selector[index + 1];
+		}
+	}
+	child
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn allele(variant: u8, dominant: bool) -> u8 {
+		if dominant { variant | DOMINANT } else { variant }
+	}
+
+	#[test]
+	fn dominant_allele_is_expressed() {
+		let mut dna = [0u8; 16];
+		dna[2] = allele(1, false);
+		dna[3] = allele(3, true);
+
+		assert_eq!(express(Locus::Fur, &dna), Gene { variant: 3, dominant: true });
+	}
+
+	#[test]
+	fn expression_does_not_depend_on_allele_order() {
+		let mut dna = [0u8; 16];
+		dna[4] = allele(9, false);
+		dna[5] = allele(2, false);
+		let mut swapped = dna;
+		swapped.swap(4, 5);
+
+		assert_eq!(express(Locus::FurColor, &dna), Gene { variant: 2, dominant: false });
+		assert_eq!(express(Locus::FurColor, &dna), express(Locus::FurColor, &swapped));
+	}
+
+	#[test]
+	fn variant_wraps_to_locus_variants() {
+		let mut dna = [0u8; 16];
+		dna[0] = allele(6, true);
+
+		assert_eq!(decode(&dna).body, Gene { variant: 2, dominant: true });
+	}
+
+	#[test]
+	fn child_gets_one_allele_from_each_parent() {
+		let dna1 = [1u8; 16];
+		let dna2 = [2u8; 16];
+		let child = inherit(&dna1, &dna2, &[0b11; 16], &[0xff; 16], Permill::zero());
+
+		for locus in 0..LOCI {
+			assert_eq!(child[locus * 2], 1);
+			assert_eq!(child[locus * 2 + 1], 2);
+		}
+	}
+
+	#[test]
+	fn certain_mutation_replaces_an_allele() {
+		let dna1 = [1u8; 16];
+		let dna2 = [2u8; 16];
+		let mut selector = [0u8; 16];
+		for locus in 0..LOCI {
+			selector[locus * 2] = 0b100;
+			selector[locus * 2 + 1] = 7;
+		}
+		let child = inherit(&dna1, &dna2, &selector, &[0xff; 16], Permill::one());
+
+		for locus in 0..LOCI {
+			assert_eq!(child[locus * 2], 1);
+			assert_eq!(child[locus * 2 + 1], 7);
+		}
+	}
+}
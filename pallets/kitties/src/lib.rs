@@ -0,0 +1,1781 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Kitties: create, breed and trade kitties.
+//!
+//! Kitties carry a genome (see `genetics`) and their lineage. Owners can sell them at a fixed
+//! price, in English or Dutch auctions, or to accepted escrowed offers, rent them out as sires,
+//! gift them and burn them. Minting reserves a deposit that is returned when the kitty is burned.
+//!
+//! Every kitty belongs to a collection. The owner of a collection sets its supply cap, mint price and
+//! allowlist, and whether its kitties can breed with kitties of other collections.
+//!
+//! The pallet is built against the FRAME release of the root `runtime` and is only part of that
+//! runtime. The `lession5` and `lession6` nodes pin older pre-release Substrate versions whose
+//! `Trait` based pallets it can not be compiled with. The module in `pallets/substrate-kitties` is
+//! the 2019 version it was ported from and is not kept feature complete with it.
+
+use codec::{Encode, Decode};
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, ensure, dispatch, transactional, Parameter,
+	traits::{Get, Currency, ReservableCurrency, ExistenceRequirement, BalanceStatus, Randomness},
+	weights::Weight,
+};
+use frame_system::{ensure_signed, ensure_root};
+use sp_io::hashing::blake2_128;
+use sp_runtime::{
+	Permill, Perbill, RuntimeDebug, DispatchError, ModuleId,
+	traits::{AtLeast32BitUnsigned, AccountIdConversion, Bounded, Hash as HashT, Member, One, Zero, Saturating},
+};
+use sp_std::prelude::*;
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+
+pub mod genetics;
+pub mod linked_item;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+use genetics::KittyTraits;
+use linked_item::{LinkedList, LinkedItem};
+pub use weights::WeightInfo;
+
+pub trait Config: frame_system::Config {
+	/// Because this pallet emits events, it depends on the runtime's definition of an event.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
+	type KittyIndex: Parameter + Member + AtLeast32BitUnsigned + Bounded + Default + Copy;
+	type CollectionId: Parameter + Member + AtLeast32BitUnsigned + Bounded + Default + Copy;
+	/// Reserved from the creator of a collection.
+	type CollectionDeposit: Get<BalanceOf<Self>>;
+	/// Pays for kitties and holds minting deposits, bids and offers.
+	type Currency: ReservableCurrency<Self::AccountId>;
+	/// Source of the DNA of new kitties.
+	type Randomness: Randomness<Self::Hash>;
+	/// Maximum number of open offers on a single kitty.
+	type MaxOffersPerKitty: Get<u32>;
+	/// Maximum length in bytes of the memo of a gift.
+	type MaxMemoLength: Get<u32>;
+	/// Maximum number of items in a single batch call.
+	type MaxBatchSize: Get<u32>;
+	/// Highest royalty a breeder can take on the resales of its kitties.
+	type MaxRoyalty: Get<Permill>;
+	/// Id of the pallet account that collects the marketplace fees.
+	type ModuleId: Get<ModuleId>;
+	/// Maximum length in bytes of the name of a kitty.
+	type MaxNameLength: Get<u32>;
+	/// Maximum length in bytes of the description of a kitty.
+	type MaxDescriptionLength: Get<u32>;
+	/// Reserved per byte of metadata, returned when the metadata is cleared.
+	type MetadataDepositPerByte: Get<BalanceOf<Self>>;
+	/// Reserved by a breeding commitment, forfeited if the commitment is not revealed in time.
+	type BreedCommitDeposit: Get<BalanceOf<Self>>;
+	/// Number of blocks after a breeding commitment it can be revealed in.
+	type RevealPeriod: Get<Self::BlockNumber>;
+	/// Maximum number of auctions, offers, loans and breeding commitments each that can end in a
+	/// single block, bounding the work of `on_initialize` and `on_finalize`.
+	type MaxScheduledPerBlock: Get<u32>;
+	/// Weight information for the extrinsics of this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Kitty<KittyIndex, BlockNumber> {
+	pub dna: [u8; 16],
+	/// `None` for kitties that were created rather than bred.
+	pub parents: Option<(KittyIndex, KittyIndex)>,
+	pub generation: u16,
+	pub birth: BlockNumber,
+}
+
+impl<KittyIndex: PartialEq + Copy, BlockNumber> Kitty<KittyIndex, BlockNumber> {
+	fn is_parent(&self, kitty_id: KittyIndex) -> bool {
+		self.parents.map(|(a, b)| a == kitty_id || b == kitty_id).unwrap_or(false)
+	}
+
+	/// Whether breeding the two kitties would be inbreeding: one is a parent of the other or they share a parent.
+	fn is_related(&self, kitty_id: KittyIndex, other: &Self, other_id: KittyIndex) -> bool {
+		if self.is_parent(other_id) || other.is_parent(kitty_id) {
+			return true;
+		}
+		match (self.parents, other.parents) {
+			(Some((a, b)), Some(_)) => other.is_parent(a) || other.is_parent(b),
+			_ => false,
+		}
+	}
+}
+
+/// A line of kitties with its own minting rules, run by its owner.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Collection<AccountId, Balance> {
+	pub owner: AccountId,
+	/// Most kitties ever created or bred in the collection, `None` for no cap.
+	pub max_supply: Option<u32>,
+	/// Kitties created or bred in the collection so far, burned ones included.
+	pub supply: u32,
+	/// Paid to the owner for every kitty created in the collection by another account.
+	pub mint_price: Balance,
+	/// Only the owner and the allowlisted accounts can create kitties.
+	pub allowlist_only: bool,
+	/// Its kitties can breed with the kitties of other collections that allow it as well.
+	pub cross_breeding: bool,
+}
+
+/// How the price of an auction is discovered.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum AuctionKind {
+	/// Ascending bids, the highest bid wins once the auction ends.
+	English,
+	/// The price falls from the start price to the reserve price, the first bid wins.
+	Dutch,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Auction<AccountId, Balance, BlockNumber> {
+	pub seller: AccountId,
+	pub kind: AuctionKind,
+	/// Lowest accepted bid of an English auction, opening price of a Dutch auction.
+	pub start_price: Balance,
+	/// Lowest price the kitty will be sold for.
+	pub reserve_price: Balance,
+	pub start: BlockNumber,
+	pub end: BlockNumber,
+	/// Current winning bid, its funds are reserved on the bidder's account.
+	pub bid: Option<(AccountId, Balance)>,
+}
+
+/// Price a buyer agrees to pay for a kitty at a fixed price.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum PriceLimit<Balance> {
+	/// Buy only at exactly this price.
+	Exact(Balance),
+	/// Buy at the asked price as long as it is not above this one.
+	Max(Balance),
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Offer<Balance, BlockNumber> {
+	/// Reserved on the account making the offer.
+	pub amount: Balance,
+	/// Last block the offer can be accepted in.
+	pub expiry: BlockNumber,
+}
+
+/// Terms an owner offers its kitty for lending on.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct LoanTerms<Balance, BlockNumber> {
+	/// Paid to the owner when the kitty is borrowed.
+	pub fee: Balance,
+	/// Number of blocks the borrower keeps the kitty.
+	pub duration: BlockNumber,
+}
+
+/// A kitty lent out. The owner keeps it in `OwnedKitties`, the borrower has custody of it in `BorrowedKitties`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Loan<AccountId, BlockNumber> {
+	pub borrower: AccountId,
+	/// Block the kitty goes back to its owner in.
+	pub end: BlockNumber,
+}
+
+/// Breeding committed to a hidden secret, bred once the secret is revealed.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct BreedCommit<KittyIndex, Balance, BlockNumber> {
+	pub parents: (KittyIndex, KittyIndex),
+	/// Reserved on the committing account.
+	pub deposit: Balance,
+	/// The secret can only be revealed in a later block.
+	pub block: BlockNumber,
+	/// Last block the secret can be revealed in.
+	pub expiry: BlockNumber,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SireListing<Balance, BlockNumber> {
+	/// Fee paid to the owner of the sire for every breeding.
+	pub price: Balance,
+	/// Last block the sire can be bred with.
+	pub expiry: BlockNumber,
+}
+
+/// Name, description and art of a kitty, set by its owner.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct KittyMetadata {
+	pub name: Vec<u8>,
+	pub description: Vec<u8>,
+	/// Hash of the off-chain art of the kitty.
+	pub content_hash: Option<[u8; 32]>,
+}
+
+impl KittyMetadata {
+	/// Number of bytes the deposit of the metadata is charged for.
+	fn deposit_bytes(&self) -> u32 {
+		let hash_len = if self.content_hash.is_some() { 32 } else { 0 };
+		(self.name.len() + self.description.len() + hash_len) as u32
+	}
+}
+
+/// Maximum number of kitties returned by a single page of the runtime API.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct KittyInfo<AccountId, KittyIndex, Balance> {
+	pub id: KittyIndex,
+	pub dna: [u8; 16],
+	/// `None` if the kitty is not for sale.
+	pub price: Option<Balance>,
+	/// Nonce of the price, to pass to `buy`.
+	pub price_nonce: u32,
+	pub owner: AccountId,
+}
+
+/// A page of the kitties owned by an account, in the order of its `OwnedKitties` list.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct KittiesPage<AccountId, KittyIndex, Balance> {
+	pub kitties: Vec<KittyInfo<AccountId, KittyIndex, Balance>>,
+	/// Cursor of the next page, `None` if this is the last one.
+	pub next: Option<KittyIndex>,
+	/// Number of kitties owned by the account.
+	pub total: u64,
+}
+
+type KittyOf<T> = Kitty<<T as Config>::KittyIndex, <T as frame_system::Config>::BlockNumber>;
+type KittyLinkedItem<T> = LinkedItem<<T as Config>::KittyIndex>;
+type OwnedKittiesList<T> = LinkedList<OwnedKitties<T>, OwnedKittiesCount<T>, <T as frame_system::Config>::AccountId, <T as Config>::KittyIndex>;
+type BorrowedKittiesList<T> = LinkedList<BorrowedKitties<T>, BorrowedKittiesCount<T>, <T as frame_system::Config>::AccountId, <T as Config>::KittyIndex>;
+type CollectionOf<T> = Collection<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+type AuctionOf<T> = Auction<<T as frame_system::Config>::AccountId, BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+decl_storage! {
+	trait Store for Module<T: Config> as Kitties {
+		pub Kitties get(fn kitty): map hasher(twox_64_concat) T::KittyIndex => Option<KittyOf<T>>;
+		pub KittiesCount get(fn kitties_count): T::KittyIndex;
+		pub OwnedKitties get(fn owned_kitties): map hasher(blake2_128_concat) (T::AccountId, Option<T::KittyIndex>) => Option<KittyLinkedItem<T>>;
+		pub OwnedKittiesCount get(fn owned_kitties_count): map hasher(blake2_128_concat) T::AccountId => u32;
+
+		pub KittyOwners get(fn kitty_owner): map hasher(twox_64_concat) T::KittyIndex => Option<T::AccountId>;
+
+		pub Collections get(fn collection): map hasher(twox_64_concat) T::CollectionId => Option<CollectionOf<T>>;
+		pub NextCollectionId get(fn next_collection_id): T::CollectionId;
+		/// Reserved from the owner of a collection, returned when it is destroyed. Collections created
+		/// at genesis have none and can not be destroyed.
+		pub CollectionDeposits get(fn collection_deposit): map hasher(twox_64_concat) T::CollectionId => Option<BalanceOf<T>>;
+		/// Kitties of a collection that are not burned.
+		pub CollectionKitties get(fn collection_kitties): map hasher(twox_64_concat) T::CollectionId => u32;
+		/// Accounts that can create kitties in a collection restricted to its allowlist.
+		pub CollectionAllowlist get(fn is_allowlisted): double_map hasher(twox_64_concat) T::CollectionId, hasher(blake2_128_concat) T::AccountId => bool;
+		/// Size of the allowlist of a collection.
+		pub CollectionAllowlistSize get(fn collection_allowlist_size): map hasher(twox_64_concat) T::CollectionId => u32;
+		/// A bred kitty belongs to the collection of its first parent.
+		pub KittyCollection get(fn kitty_collection): map hasher(twox_64_concat) T::KittyIndex => T::CollectionId;
+
+		pub KittyPrices get(fn kitty_price): map hasher(twox_64_concat) T::KittyIndex => Option<BalanceOf<T>>;
+		/// Bumped on every `ask`, a buy must name the nonce of the price it agreed to.
+		pub KittyPriceNonces get(fn price_nonce): map hasher(twox_64_concat) T::KittyIndex => u32;
+		/// Share of every fixed price sale paid to the pallet account.
+		pub MarketplaceFee get(fn marketplace_fee) config(): Permill;
+
+		/// Reserved from the minting account for every new kitty, returned when the kitty is burned.
+		pub MintDeposit get(fn mint_deposit) config(): BalanceOf<T>;
+		/// Most kitties an account can mint up to, 0 for no limit.
+		pub MaxKittiesPerAccount get(fn max_kitties_per_account) config(): u32;
+		/// Account that created or bred a kitty, it earns the royalties of its resales.
+		pub KittyBreeders get(fn kitty_breeder): map hasher(twox_64_concat) T::KittyIndex => Option<T::AccountId>;
+		/// Share of every resale price of a kitty paid to its breeder.
+		pub KittyRoyalties get(fn kitty_royalty): map hasher(twox_64_concat) T::KittyIndex => Permill;
+		pub Metadata get(fn metadata): map hasher(twox_64_concat) T::KittyIndex => Option<KittyMetadata>;
+		/// Account that reserved the deposit of the metadata of a kitty and the amount.
+		pub MetadataDeposits get(fn metadata_deposit): map hasher(twox_64_concat) T::KittyIndex => Option<(T::AccountId, BalanceOf<T>)>;
+		pub KittyDeposits get(fn kitty_deposit): map hasher(twox_64_concat) T::KittyIndex => Option<(T::AccountId, BalanceOf<T>)>;
+
+		pub Auctions get(fn auction): map hasher(twox_64_concat) T::KittyIndex => Option<AuctionOf<T>>;
+		/// Auctions to settle at the end of a block. Entries can be stale, the auction is checked again on settlement.
+		pub AuctionsEndingAt get(fn auctions_ending_at): map hasher(twox_64_concat) T::BlockNumber => Vec<T::KittyIndex>;
+
+		pub Offers get(fn offer): map hasher(blake2_128_concat) (T::KittyIndex, T::AccountId) => Option<Offer<BalanceOf<T>, T::BlockNumber>>;
+		pub KittyOffers get(fn kitty_offers): map hasher(twox_64_concat) T::KittyIndex => Vec<T::AccountId>;
+		/// Offers to sweep at the end of a block, one entry per pending offer.
+		pub OffersExpiringAt get(fn offers_expiring_at): map hasher(twox_64_concat) T::BlockNumber => Vec<(T::KittyIndex, T::AccountId)>;
+
+		/// Account allowed to transfer a kitty on behalf of its owner, cleared when the kitty changes hands.
+		pub KittyApprovals get(fn kitty_approval): map hasher(twox_64_concat) T::KittyIndex => Option<T::AccountId>;
+		/// Operators allowed to transfer and approve all the kitties of an owner. \[owner, operator\]
+		pub OperatorApprovals get(fn is_approved_for_all): map hasher(blake2_128_concat) (T::AccountId, T::AccountId) => bool;
+
+		/// Kitties their owner is willing to lend.
+		pub LoanOffers get(fn loan_offer): map hasher(twox_64_concat) T::KittyIndex => Option<LoanTerms<BalanceOf<T>, T::BlockNumber>>;
+		/// Kitties lent out. A kitty is in the `BorrowedKitties` list of its borrower for as long as it is in here.
+		pub Loans get(fn loan): map hasher(twox_64_concat) T::KittyIndex => Option<Loan<T::AccountId, T::BlockNumber>>;
+		pub BorrowedKitties get(fn borrowed_kitties): map hasher(blake2_128_concat) (T::AccountId, Option<T::KittyIndex>) => Option<KittyLinkedItem<T>>;
+		pub BorrowedKittiesCount get(fn borrowed_kitties_count): map hasher(blake2_128_concat) T::AccountId => u32;
+		/// Loans to end at the start of a block.
+		pub LoansEndingAt get(fn loans_ending_at): map hasher(twox_64_concat) T::BlockNumber => Vec<T::KittyIndex>;
+
+		/// Kitties that can be bred with by anyone paying the fee to their owner.
+		pub SireListings get(fn sire_listing): map hasher(twox_64_concat) T::KittyIndex => Option<SireListing<BalanceOf<T>, T::BlockNumber>>;
+
+		/// Pending breeding commitments by committing account and commitment.
+		pub BreedCommits get(fn breed_commit): map hasher(blake2_128_concat) (T::AccountId, T::Hash) => Option<BreedCommit<T::KittyIndex, BalanceOf<T>, T::BlockNumber>>;
+		/// Commitment the kitty is a parent in. Until it is revealed or forfeited the kitty can not change
+		/// hands, be sold, lent or bred otherwise, so the reveal can not fail on a move of someone else.
+		pub CommittedKitties get(fn committed_kitty): map hasher(twox_64_concat) T::KittyIndex => Option<(T::AccountId, T::Hash)>;
+		/// Commitments to forfeit at the end of a block. Entries can be stale, the commitment is checked again.
+		pub CommitsExpiringAt get(fn commits_expiring_at): map hasher(twox_64_concat) T::BlockNumber => Vec<(T::AccountId, T::Hash)>;
+		/// Randomness the commitments of a block are revealed with, by their expiry. It is drawn at the
+		/// start of the next block, after the commitments are made and before any can be revealed.
+		pub CommitSeeds get(fn commit_seed): map hasher(twox_64_concat) T::BlockNumber => Option<T::Hash>;
+		/// Block to draw the next seed at and the expiry it is stored under.
+		pub NextCommitSeed get(fn next_commit_seed): Option<(T::BlockNumber, T::BlockNumber)>;
+
+		/// Chance of an allele of a newborn kitty to mutate.
+		pub MutationRate get(fn mutation_rate) config(): Permill;
+
+		/// Cooldown of a generation 0 parent, a parent of generation n waits n + 1 times as long.
+		pub BreedingCooldown get(fn breeding_cooldown) config(): T::BlockNumber;
+		/// First block a kitty can breed again.
+		pub KittyCooldowns get(fn kitty_cooldown): map hasher(twox_64_concat) T::KittyIndex => T::BlockNumber;
+	}
+	add_extra_genesis {
+		/// Open collections without a deposit, by owner, supply cap and mint price.
+		config(collections): Vec<(T::AccountId, Option<u32>, BalanceOf<T>)>;
+		build(|config: &GenesisConfig<T>| {
+			// The pallet account collects the marketplace fees. It has to exist to receive fees below
+			// the existential deposit.
+			let _ = T::Currency::make_free_balance_be(&Module::<T>::account_id(), T::Currency::minimum_balance());
+
+			for (owner, max_supply, mint_price) in &config.collections {
+				let collection_id = Module::<T>::next_collection_id();
+				Collections::<T>::insert(collection_id, Collection {
+					owner: owner.clone(),
+					max_supply: *max_supply,
+					supply: 0,
+					mint_price: *mint_price,
+					allowlist_only: false,
+					cross_breeding: false,
+				});
+				NextCollectionId::<T>::put(collection_id + One::one());
+			}
+		});
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Config>::AccountId,
+		BlockNumber = <T as frame_system::Config>::BlockNumber,
+		Hash = <T as frame_system::Config>::Hash,
+		KittyIndex = <T as Config>::KittyIndex,
+		CollectionId = <T as Config>::CollectionId,
+		Balance = BalanceOf<T>,
+	{
+		/// A kitty is created. \[owner, kitty_id\]
+		Created(AccountId, KittyIndex),
+		/// A kitty is transferred. \[from, to, kitty_id\]
+		Transferred(AccountId, AccountId, KittyIndex),
+		/// A kitty is given away with a memo. \[from, to, kitty_id, memo\]
+		Gifted(AccountId, AccountId, KittyIndex, Vec<u8>),
+		/// A kitty is burned. \[owner, kitty_id\]
+		Burned(AccountId, KittyIndex),
+		/// A kitty is available for sale. \[owner, kitty_id, price\]
+		Ask(AccountId, KittyIndex, Option<Balance>),
+		/// A kitty is sold. \[from, to, kitty_id, price\]
+		Sold(AccountId, AccountId, KittyIndex, Balance),
+		/// An auction is opened. \[seller, kitty_id, kind, end\]
+		AuctionCreated(AccountId, KittyIndex, AuctionKind, BlockNumber),
+		/// A bid is placed. \[bidder, kitty_id, amount\]
+		Bid(AccountId, KittyIndex, Balance),
+		/// An auction without bids is cancelled. \[seller, kitty_id\]
+		AuctionCancelled(AccountId, KittyIndex),
+		/// An auction is settled. \[seller, buyer, kitty_id, price\]
+		AuctionSettled(AccountId, AccountId, KittyIndex, Balance),
+		/// An auction ended without a bid meeting the reserve price. \[seller, kitty_id\]
+		AuctionFailed(AccountId, KittyIndex),
+		/// An offer is made. \[buyer, kitty_id, amount, expiry\]
+		OfferMade(AccountId, KittyIndex, Balance, BlockNumber),
+		/// An offer is withdrawn. \[buyer, kitty_id\]
+		OfferWithdrawn(AccountId, KittyIndex),
+		/// An offer is accepted. \[from, to, kitty_id, amount\]
+		OfferAccepted(AccountId, AccountId, KittyIndex, Balance),
+		/// An offer expired and its funds are released. \[buyer, kitty_id\]
+		OfferExpired(AccountId, KittyIndex),
+		/// A kitty is available as a sire. \[owner, kitty_id, price, expiry\]
+		SireListed(AccountId, KittyIndex, Balance, BlockNumber),
+		/// A sire listing is cancelled. \[owner, kitty_id\]
+		SireCancelled(AccountId, KittyIndex),
+		/// A listed sire is bred with. \[breeder, sire owner, sire_id, price\]
+		Sired(AccountId, AccountId, KittyIndex, Balance),
+		/// The approval of a kitty is set or cleared. \[owner, spender, kitty_id\]
+		Approval(AccountId, Option<AccountId>, KittyIndex),
+		/// An operator is approved or revoked for all the kitties of an owner. \[owner, operator, approved\]
+		ApprovalForAll(AccountId, AccountId, bool),
+		/// The royalty of a kitty is changed by its breeder. \[breeder, kitty_id, royalty\]
+		RoyaltySet(AccountId, KittyIndex, Permill),
+		/// A share of a resale is paid to the breeder. \[breeder, kitty_id, amount\]
+		RoyaltyPaid(AccountId, KittyIndex, Balance),
+		/// The marketplace fee of a sale is paid to the pallet account. \[buyer, kitty_id, amount\]
+		MarketplaceFeePaid(AccountId, KittyIndex, Balance),
+		/// A kitty is offered for lending. \[owner, kitty_id, fee, duration\]
+		LoanOffered(AccountId, KittyIndex, Balance, BlockNumber),
+		/// A loan offer is withdrawn. \[owner, kitty_id\]
+		LoanOfferCancelled(AccountId, KittyIndex),
+		/// A kitty is borrowed. \[owner, borrower, kitty_id, end\]
+		Borrowed(AccountId, AccountId, KittyIndex, BlockNumber),
+		/// A loan ended and the kitty is back with its owner. \[owner, borrower, kitty_id\]
+		LoanEnded(AccountId, AccountId, KittyIndex),
+		/// The metadata of a kitty is set. \[owner, kitty_id\]
+		MetadataSet(AccountId, KittyIndex),
+		/// The metadata of a kitty is cleared. \[owner, kitty_id\]
+		MetadataCleared(AccountId, KittyIndex),
+		/// A breeding is committed. \[breeder, commitment, expiry\]
+		BreedCommitted(AccountId, Hash, BlockNumber),
+		/// A commitment was not revealed in time and its deposit is forfeited. \[breeder, commitment, deposit\]
+		BreedCommitForfeited(AccountId, Hash, Balance),
+		/// A collection is created. \[owner, collection_id\]
+		CollectionCreated(AccountId, CollectionId),
+		/// The mint price, allowlist restriction or cross breeding of a collection changed. \[collection_id\]
+		CollectionUpdated(CollectionId),
+		/// An account is added to or removed from the allowlist of a collection. \[collection_id, account, allowed\]
+		AllowlistSet(CollectionId, AccountId, bool),
+		/// A collection is destroyed and its deposit returned. \[collection_id\]
+		CollectionDestroyed(CollectionId),
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Config> {
+		/// All kitty ids are taken.
+		KittiesCountOverflow,
+		/// The account owns `MaxKittiesPerAccount` kitties.
+		TooManyKitties,
+		/// The account can not reserve the creation deposit.
+		InsufficientDeposit,
+		InvalidKittyId,
+		RequireDifferentParent,
+		/// The sender does not own the kitty.
+		NotOwner,
+		KittyOnCooldown,
+		/// One parent is a parent of the other or they share a parent.
+		ParentsAreRelated,
+		KittyInAuction,
+		KittyNotForSale,
+		/// The asked price is above the maximum price of the buyer.
+		PriceTooLow,
+		/// The sire fee is above the maximum fee of the breeder.
+		SireFeeTooHigh,
+		/// The asked price is not the exact price of the buyer.
+		PriceMismatch,
+		/// The price was changed since the buyer read it.
+		PriceChanged,
+		MemoTooLong,
+		AuctionNotFound,
+		AuctionEndInPast,
+		StartPriceBelowReserve,
+		SellerCannotBid,
+		AuctionEnded,
+		BidTooLow,
+		NotSeller,
+		AuctionHasBid,
+		/// Offers and sire fees can not be paid to oneself.
+		OwnKitty,
+		/// The expiry of an offer or sire listing is in the past.
+		Expired,
+		TooManyOffers,
+		OfferNotFound,
+		NotListedAsSire,
+		/// The sender can not pay both the sire fee and the creation deposit.
+		CannotAffordSire,
+		/// The batch holds more than `MaxBatchSize` items.
+		BatchTooLarge,
+		/// The sender is neither the owner of the kitty nor approved to move it.
+		NotApproved,
+		/// Only the breeder of a kitty can set its royalty.
+		NotBreeder,
+		/// The royalty is above `MaxRoyalty`.
+		RoyaltyTooHigh,
+		/// The kitty is lent out, its owner can not move it until the loan ends.
+		KittyOnLoan,
+		NotOfferedForLoan,
+		/// Loans last at least one block.
+		InvalidLoanDuration,
+		/// The loan terms changed since the borrower read them.
+		LoanFeeMismatch,
+		NameTooLong,
+		DescriptionTooLong,
+		/// The sender can not reserve the deposit of the metadata.
+		InsufficientMetadataDeposit,
+		NoMetadata,
+		/// The sender already has a pending commitment with this hash.
+		CommitExists,
+		CommitNotFound,
+		/// A commitment can only be revealed in a later block than it was made in.
+		RevealTooEarly,
+		/// The sender can not reserve the deposit of the commitment.
+		InsufficientCommitDeposit,
+		/// The kitty is a parent in a pending breeding commitment.
+		KittyCommitted,
+		/// A borrowed parent goes back to its owner before the commitment can no longer be revealed.
+		LoanEndsBeforeReveal,
+		/// All collection ids are taken.
+		CollectionIdOverflow,
+		CollectionNotFound,
+		NotCollectionOwner,
+		/// The collection reached its supply cap.
+		CollectionFull,
+		/// The collection is restricted to its allowlist.
+		NotAllowlisted,
+		/// The parents are in different collections and one of them does not allow cross breeding.
+		CrossBreedingNotAllowed,
+		/// The collection was created at genesis without a deposit.
+		PermanentCollection,
+		/// The collection still has kitties that are not burned.
+		CollectionNotEmpty,
+		/// The allowlist of the collection has to be cleared first.
+		AllowlistNotEmpty,
+		/// Too many auctions, offers, loans or commitments already end in this block.
+		TooManyScheduled,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Config> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		const MaxOffersPerKitty: u32 = T::MaxOffersPerKitty::get();
+		const MaxMemoLength: u32 = T::MaxMemoLength::get();
+		const MaxBatchSize: u32 = T::MaxBatchSize::get();
+		const MaxRoyalty: Permill = T::MaxRoyalty::get();
+		const ModuleId: ModuleId = T::ModuleId::get();
+		const MaxNameLength: u32 = T::MaxNameLength::get();
+		const MaxDescriptionLength: u32 = T::MaxDescriptionLength::get();
+		const MetadataDepositPerByte: BalanceOf<T> = T::MetadataDepositPerByte::get();
+		const BreedCommitDeposit: BalanceOf<T> = T::BreedCommitDeposit::get();
+		const RevealPeriod: T::BlockNumber = T::RevealPeriod::get();
+		const CollectionDeposit: BalanceOf<T> = T::CollectionDeposit::get();
+		const MaxScheduledPerBlock: u32 = T::MaxScheduledPerBlock::get();
+
+		fn deposit_event() = default;
+
+		/// Creates a kitty in `collection_id`, paying the mint price of the collection to its owner.
+		#[weight = T::WeightInfo::create()]
+		#[transactional]
+		pub fn create(origin, collection_id: T::CollectionId) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let kitty_id = Self::ensure_can_mint(&sender, collection_id)?;
+			Self::pay_mint_price(&sender, collection_id)?;
+
+			let dna = Self::random_value(&sender);
+			let kitty = Kitty {
+				dna,
+				parents: None,
+				generation: 0,
+				birth: frame_system::Module::<T>::block_number(),
+			};
+			Self::insert_kitty(&sender, kitty_id, collection_id, kitty)?;
+
+			Self::deposit_event(RawEvent::Created(sender, kitty_id));
+			Ok(())
+		}
+
+		/// Breeds a kitty into the collection of the first parent, paying its mint price.
+		#[weight = T::WeightInfo::breed()]
+		#[transactional]
+		pub fn breed(origin, kitty_id_1: T::KittyIndex, kitty_id_2: T::KittyIndex) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let new_kitty_id = Self::do_breed(&sender, kitty_id_1, kitty_id_2)?;
+
+			Self::deposit_event(RawEvent::Created(sender, new_kitty_id));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::transfer()]
+		pub fn transfer(origin, to: T::AccountId, kitty_id: T::KittyIndex) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_transferable(&sender, kitty_id)?;
+			Self::do_transfer(&sender, &to, kitty_id);
+
+			Self::deposit_event(RawEvent::Transferred(sender, to, kitty_id));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::gift(memo.len() as u32)]
+		pub fn gift(origin, to: T::AccountId, kitty_id: T::KittyIndex, memo: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(memo.len() <= T::MaxMemoLength::get() as usize, Error::<T>::MemoTooLong);
+			Self::ensure_transferable(&sender, kitty_id)?;
+
+			Self::do_transfer(&sender, &to, kitty_id);
+
+			Self::deposit_event(RawEvent::Gifted(sender, to, kitty_id, memo));
+			Ok(())
+		}
+
+		/// Releases a kitty to the wild, removing it for good. Open offers on it and its creation deposit are refunded.
+		#[weight = T::WeightInfo::burn(T::MaxOffersPerKitty::get())]
+		pub fn burn(origin, kitty_id: T::KittyIndex) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_transferable(&sender, kitty_id)?;
+
+			Self::do_burn(&sender, kitty_id);
+
+			Self::deposit_event(RawEvent::Burned(sender, kitty_id));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::ask()]
+		pub fn ask(origin, kitty_id: T::KittyIndex, price: Option<BalanceOf<T>>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_transferable(&sender, kitty_id)?;
+
+			Self::set_price(kitty_id, price);
+
+			Self::deposit_event(RawEvent::Ask(sender, kitty_id, price));
+			Ok(())
+		}
+
+		/// Buys a kitty at its asked price. `price_nonce` is the nonce of the price the buyer agreed to,
+		/// the buy fails if the seller changed the price since, even within the same block.
+		#[weight = T::WeightInfo::buy()]
+		#[transactional]
+		pub fn buy(origin, kitty_id: T::KittyIndex, limit: PriceLimit<BalanceOf<T>>, price_nonce: u32) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner != sender, Error::<T>::OwnKitty);
+			let kitty_price = Self::kitty_price(kitty_id).ok_or(Error::<T>::KittyNotForSale)?;
+			ensure!(price_nonce == Self::price_nonce(kitty_id), Error::<T>::PriceChanged);
+			match limit {
+				PriceLimit::Exact(price) => ensure!(price == kitty_price, Error::<T>::PriceMismatch),
+				PriceLimit::Max(price) => ensure!(price >= kitty_price, Error::<T>::PriceTooLow),
+			}
+
+			// The fee and the royalty are both shares of the full price.
+			let mut proceeds = kitty_price;
+			let fee = Self::marketplace_fee() * kitty_price;
+			if !fee.is_zero() {
+				T::Currency::transfer(&sender, &Self::account_id(), fee, ExistenceRequirement::KeepAlive)?;
+				proceeds = proceeds.saturating_sub(fee);
+				Self::deposit_event(RawEvent::MarketplaceFeePaid(sender.clone(), kitty_id, fee));
+			}
+			if let Some((breeder, royalty)) = Self::royalty_of(kitty_id, &owner, kitty_price) {
+				let royalty = royalty.min(proceeds);
+				T::Currency::transfer(&sender, &breeder, royalty, ExistenceRequirement::KeepAlive)?;
+				proceeds -= royalty;
+				Self::deposit_event(RawEvent::RoyaltyPaid(breeder, kitty_id, royalty));
+			}
+			T::Currency::transfer(&sender, &owner, proceeds, ExistenceRequirement::KeepAlive)?;
+
+			Self::do_transfer(&owner, &sender, kitty_id);
+
+			Self::deposit_event(RawEvent::Sold(owner, sender, kitty_id, kitty_price));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::create_auction()]
+		pub fn create_auction(
+			origin,
+			kitty_id: T::KittyIndex,
+			kind: AuctionKind,
+			start_price: BalanceOf<T>,
+			reserve_price: BalanceOf<T>,
+			end: T::BlockNumber,
+		) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_transferable(&sender, kitty_id)?;
+
+			let now = frame_system::Module::<T>::block_number();
+			ensure!(end > now, Error::<T>::AuctionEndInPast);
+			Self::ensure_schedulable(AuctionsEndingAt::<T>::decode_len(end))?;
+			if kind == AuctionKind::Dutch {
+				ensure!(start_price >= reserve_price, Error::<T>::StartPriceBelowReserve);
+			}
+
+			// An auctioned kitty can not be bought at a fixed price at the same time.
+			KittyPrices::<T>::remove(kitty_id);
+
+			Auctions::<T>::insert(kitty_id, Auction {
+				seller: sender.clone(),
+				kind,
+				start_price,
+				reserve_price,
+				start: now,
+				end,
+				bid: None,
+			});
+			AuctionsEndingAt::<T>::append(end, kitty_id);
+
+			Self::deposit_event(RawEvent::AuctionCreated(sender, kitty_id, kind, end));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::bid()]
+		pub fn bid(origin, kitty_id: T::KittyIndex, amount: BalanceOf<T>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut auction = Self::auction(kitty_id).ok_or(Error::<T>::AuctionNotFound)?;
+			ensure!(auction.seller != sender, Error::<T>::SellerCannotBid);
+
+			let now = frame_system::Module::<T>::block_number();
+			ensure!(now < auction.end, Error::<T>::AuctionEnded);
+
+			let amount = match auction.kind {
+				AuctionKind::English => {
+					ensure!(amount >= auction.start_price, Error::<T>::BidTooLow);
+					if let Some((_, highest)) = auction.bid {
+						ensure!(amount > highest, Error::<T>::BidTooLow);
+					}
+
+					T::Currency::reserve(&sender, amount)?;
+					if let Some((bidder, highest)) = auction.bid.take() {
+						T::Currency::unreserve(&bidder, highest);
+					}
+					amount
+				},
+				AuctionKind::Dutch => {
+					let price = Self::dutch_price(&auction, now);
+					ensure!(amount >= price, Error::<T>::BidTooLow);
+					Self::ensure_schedulable(AuctionsEndingAt::<T>::decode_len(now))?;
+
+					T::Currency::reserve(&sender, price)?;
+					// The first bid wins, settle at the end of this block.
+					auction.end = now;
+					AuctionsEndingAt::<T>::append(now, kitty_id);
+					price
+				},
+			};
+
+			auction.bid = Some((sender.clone(), amount));
+			Auctions::<T>::insert(kitty_id, auction);
+
+			Self::deposit_event(RawEvent::Bid(sender, kitty_id, amount));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::cancel_auction()]
+		pub fn cancel_auction(origin, kitty_id: T::KittyIndex) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let auction = Self::auction(kitty_id).ok_or(Error::<T>::AuctionNotFound)?;
+			ensure!(auction.seller == sender, Error::<T>::NotSeller);
+			ensure!(auction.bid.is_none(), Error::<T>::AuctionHasBid);
+
+			Auctions::<T>::remove(kitty_id);
+
+			Self::deposit_event(RawEvent::AuctionCancelled(sender, kitty_id));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::make_offer()]
+		pub fn make_offer(origin, kitty_id: T::KittyIndex, amount: BalanceOf<T>, expiry: T::BlockNumber) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner != sender, Error::<T>::OwnKitty);
+			ensure!(expiry >= frame_system::Module::<T>::block_number(), Error::<T>::Expired);
+
+			let previous = Self::offer((kitty_id, sender.clone()));
+			if previous.is_none() {
+				ensure!(
+					Self::kitty_offers(kitty_id).len() < T::MaxOffersPerKitty::get() as usize,
+					Error::<T>::TooManyOffers
+				);
+			}
+			if previous.as_ref().map_or(true, |previous| previous.expiry != expiry) {
+				Self::ensure_schedulable(OffersExpiringAt::<T>::decode_len(expiry))?;
+			}
+
+			T::Currency::reserve(&sender, amount)?;
+			let scheduled = match previous {
+				Some(previous) => {
+					T::Currency::unreserve(&sender, previous.amount);
+					if previous.expiry != expiry {
+						Self::unschedule_offer(previous.expiry, kitty_id, &sender);
+					}
+					previous.expiry == expiry
+				},
+				None => {
+					KittyOffers::<T>::append(kitty_id, sender.clone());
+					false
+				},
+			};
+
+			Offers::<T>::insert((kitty_id, sender.clone()), Offer { amount, expiry });
+			if !scheduled {
+				OffersExpiringAt::<T>::append(expiry, (kitty_id, sender.clone()));
+			}
+
+			Self::deposit_event(RawEvent::OfferMade(sender, kitty_id, amount, expiry));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::withdraw_offer()]
+		pub fn withdraw_offer(origin, kitty_id: T::KittyIndex) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let offer = Self::remove_offer(kitty_id, &sender).ok_or(Error::<T>::OfferNotFound)?;
+			T::Currency::unreserve(&sender, offer.amount);
+
+			Self::deposit_event(RawEvent::OfferWithdrawn(sender, kitty_id));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::accept_offer()]
+		#[transactional]
+		pub fn accept_offer(origin, kitty_id: T::KittyIndex, buyer: T::AccountId) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_transferable(&sender, kitty_id)?;
+
+			let offer = Self::offer((kitty_id, buyer.clone())).ok_or(Error::<T>::OfferNotFound)?;
+			ensure!(offer.expiry >= frame_system::Module::<T>::block_number(), Error::<T>::Expired);
+
+			// Everything that can fail is checked, move the funds first and then the kitty.
+			Self::pay_reserved(&buyer, &sender, kitty_id, offer.amount)?;
+			Self::remove_offer(kitty_id, &buyer);
+			Self::do_transfer(&sender, &buyer, kitty_id);
+
+			Self::deposit_event(RawEvent::OfferAccepted(sender, buyer, kitty_id, offer.amount));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::list_sire()]
+		pub fn list_sire(origin, kitty_id: T::KittyIndex, price: BalanceOf<T>, expiry: T::BlockNumber) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Self::is_owner(&sender, kitty_id), Error::<T>::NotOwner);
+			ensure!(!Loans::<T>::contains_key(kitty_id), Error::<T>::KittyOnLoan);
+			ensure!(!CommittedKitties::<T>::contains_key(kitty_id), Error::<T>::KittyCommitted);
+			ensure!(expiry >= frame_system::Module::<T>::block_number(), Error::<T>::Expired);
+
+			SireListings::<T>::insert(kitty_id, SireListing { price, expiry });
+
+			Self::deposit_event(RawEvent::SireListed(sender, kitty_id, price, expiry));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::cancel_sire()]
+		pub fn cancel_sire(origin, kitty_id: T::KittyIndex) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Self::is_owner(&sender, kitty_id), Error::<T>::NotOwner);
+			ensure!(SireListings::<T>::contains_key(kitty_id), Error::<T>::NotListedAsSire);
+
+			SireListings::<T>::remove(kitty_id);
+
+			Self::deposit_event(RawEvent::SireCancelled(sender, kitty_id));
+			Ok(())
+		}
+
+		/// Breeds `kitty_id` with a listed sire, paying its fee to the owner of the sire. Fails if the
+		/// fee was raised above `max_fee` after the call was signed.
+		#[weight = T::WeightInfo::breed_with_sire()]
+		#[transactional]
+		pub fn breed_with_sire(
+			origin,
+			kitty_id: T::KittyIndex,
+			sire_id: T::KittyIndex,
+			max_fee: BalanceOf<T>,
+		) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let listing = Self::sire_listing(sire_id).ok_or(Error::<T>::NotListedAsSire)?;
+			ensure!(listing.expiry >= frame_system::Module::<T>::block_number(), Error::<T>::Expired);
+			ensure!(listing.price <= max_fee, Error::<T>::SireFeeTooHigh);
+
+			let sire_owner = Self::kitty_owner(sire_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(sire_owner != sender, Error::<T>::OwnKitty);
+
+			// Everything that can fail is checked before the fee is paid.
+			let (kitty, sire) = Self::check_breed(&sender, &sire_owner, kitty_id, sire_id)?;
+			ensure!(
+				T::Currency::free_balance(&sender) >= listing.price.saturating_add(Self::mint_deposit()),
+				Error::<T>::CannotAffordSire
+			);
+			T::Currency::transfer(&sender, &sire_owner, listing.price, ExistenceRequirement::KeepAlive)?;
+			let new_kitty_id = Self::breed_child(&sender, kitty_id, kitty, sire_id, sire, Self::random_value(&sender))?;
+
+			Self::deposit_event(RawEvent::Sired(sender.clone(), sire_owner, sire_id, listing.price));
+			Self::deposit_event(RawEvent::Created(sender, new_kitty_id));
+			Ok(())
+		}
+
+		/// Allows `spender` to transfer the kitty until it changes hands, `None` clears the approval.
+		/// Can be called by the owner or one of its operators.
+		#[weight = T::WeightInfo::approve()]
+		pub fn approve(origin, spender: Option<T::AccountId>, kitty_id: T::KittyIndex) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(sender == owner || Self::is_approved_for_all((owner.clone(), sender)), Error::<T>::NotApproved);
+
+			if let Some(ref spender) = spender {
+				KittyApprovals::<T>::insert(kitty_id, spender);
+			} else {
+				KittyApprovals::<T>::remove(kitty_id);
+			}
+
+			Self::deposit_event(RawEvent::Approval(owner, spender, kitty_id));
+			Ok(())
+		}
+
+		/// Allows or forbids `operator` to transfer and approve every kitty of the sender, including kitties it gets later.
+		#[weight = T::WeightInfo::set_approval_for_all()]
+		pub fn set_approval_for_all(origin, operator: T::AccountId, approved: bool) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			if approved {
+				OperatorApprovals::<T>::insert((sender.clone(), operator.clone()), true);
+			} else {
+				OperatorApprovals::<T>::remove((sender.clone(), operator.clone()));
+			}
+
+			Self::deposit_event(RawEvent::ApprovalForAll(sender, operator, approved));
+			Ok(())
+		}
+
+		/// Transfers a kitty of `from`, the sender must be `from`, approved for the kitty or an operator of `from`.
+		#[weight = T::WeightInfo::transfer_from()]
+		pub fn transfer_from(origin, from: T::AccountId, to: T::AccountId, kitty_id: T::KittyIndex) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_transferable(&from, kitty_id)?;
+			ensure!(Self::is_approved(&sender, &from, kitty_id), Error::<T>::NotApproved);
+
+			Self::do_transfer(&from, &to, kitty_id);
+
+			Self::deposit_event(RawEvent::Transferred(from, to, kitty_id));
+			Ok(())
+		}
+
+		/// Sets the share of every future resale of the kitty paid to its breeder, up to `MaxRoyalty`.
+		/// The breeder keeps the royalty after the kitty is sold.
+		#[weight = T::WeightInfo::set_royalty()]
+		pub fn set_royalty(origin, kitty_id: T::KittyIndex, royalty: Permill) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Self::kitty_breeder(kitty_id).as_ref() == Some(&sender), Error::<T>::NotBreeder);
+			ensure!(royalty <= T::MaxRoyalty::get(), Error::<T>::RoyaltyTooHigh);
+
+			KittyRoyalties::<T>::insert(kitty_id, royalty);
+
+			Self::deposit_event(RawEvent::RoyaltySet(sender, kitty_id, royalty));
+			Ok(())
+		}
+
+		/// Offers the kitty for lending, the first account to `borrow` it pays `fee` and keeps it for `duration` blocks.
+		#[weight = T::WeightInfo::offer_loan()]
+		pub fn offer_loan(origin, kitty_id: T::KittyIndex, fee: BalanceOf<T>, duration: T::BlockNumber) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_transferable(&sender, kitty_id)?;
+			ensure!(!duration.is_zero(), Error::<T>::InvalidLoanDuration);
+
+			LoanOffers::<T>::insert(kitty_id, LoanTerms { fee, duration });
+
+			Self::deposit_event(RawEvent::LoanOffered(sender, kitty_id, fee, duration));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::cancel_loan_offer()]
+		pub fn cancel_loan_offer(origin, kitty_id: T::KittyIndex) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Self::is_owner(&sender, kitty_id), Error::<T>::NotOwner);
+			ensure!(LoanOffers::<T>::contains_key(kitty_id), Error::<T>::NotOfferedForLoan);
+
+			LoanOffers::<T>::remove(kitty_id);
+
+			Self::deposit_event(RawEvent::LoanOfferCancelled(sender, kitty_id));
+			Ok(())
+		}
+
+		/// Borrows a kitty offered for lending at `fee`. The borrower can breed with it until the loan
+		/// ends, nobody can sell, transfer or burn it in the meantime.
+		#[weight = T::WeightInfo::borrow()]
+		#[transactional]
+		pub fn borrow(origin, kitty_id: T::KittyIndex, fee: BalanceOf<T>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let terms = Self::loan_offer(kitty_id).ok_or(Error::<T>::NotOfferedForLoan)?;
+			ensure!(terms.fee == fee, Error::<T>::LoanFeeMismatch);
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner != sender, Error::<T>::OwnKitty);
+			// The offer is only made while the kitty is transferable, check again in case it was auctioned since.
+			Self::ensure_transferable(&owner, kitty_id)?;
+			let end = frame_system::Module::<T>::block_number().saturating_add(terms.duration);
+			Self::ensure_schedulable(LoansEndingAt::<T>::decode_len(end))?;
+
+			T::Currency::transfer(&sender, &owner, fee, ExistenceRequirement::KeepAlive)?;
+
+			LoanOffers::<T>::remove(kitty_id);
+			// The owner can not sell or rent out the kitty as a sire while it is lent.
+			KittyPrices::<T>::remove(kitty_id);
+			SireListings::<T>::remove(kitty_id);
+			Loans::<T>::insert(kitty_id, Loan { borrower: sender.clone(), end });
+			BorrowedKittiesList::<T>::append(&sender, kitty_id);
+			LoansEndingAt::<T>::append(end, kitty_id);
+
+			Self::deposit_event(RawEvent::Borrowed(owner, sender, kitty_id, end));
+			Ok(())
+		}
+
+		/// Sets the metadata of a kitty, replacing the previous one. The sender reserves a deposit per
+		/// byte and the deposit of the previous metadata goes back to whoever paid it.
+		#[weight = T::WeightInfo::set_metadata()]
+		#[transactional]
+		pub fn set_metadata(
+			origin,
+			kitty_id: T::KittyIndex,
+			name: Vec<u8>,
+			description: Vec<u8>,
+			content_hash: Option<[u8; 32]>,
+		) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Self::is_owner(&sender, kitty_id), Error::<T>::NotOwner);
+			ensure!(name.len() <= T::MaxNameLength::get() as usize, Error::<T>::NameTooLong);
+			ensure!(description.len() <= T::MaxDescriptionLength::get() as usize, Error::<T>::DescriptionTooLong);
+
+			let metadata = KittyMetadata { name, description, content_hash };
+			let deposit = T::MetadataDepositPerByte::get().saturating_mul(metadata.deposit_bytes().into());
+
+			Self::release_metadata_deposit(kitty_id);
+			T::Currency::reserve(&sender, deposit).map_err(|_| Error::<T>::InsufficientMetadataDeposit)?;
+			if !deposit.is_zero() {
+				MetadataDeposits::<T>::insert(kitty_id, (sender.clone(), deposit));
+			}
+			Metadata::<T>::insert(kitty_id, metadata);
+
+			Self::deposit_event(RawEvent::MetadataSet(sender, kitty_id));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::clear_metadata()]
+		pub fn clear_metadata(origin, kitty_id: T::KittyIndex) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Self::is_owner(&sender, kitty_id), Error::<T>::NotOwner);
+			ensure!(Metadata::<T>::contains_key(kitty_id), Error::<T>::NoMetadata);
+
+			Metadata::<T>::remove(kitty_id);
+			Self::release_metadata_deposit(kitty_id);
+
+			Self::deposit_event(RawEvent::MetadataCleared(sender, kitty_id));
+			Ok(())
+		}
+
+		/// First step of breeding with randomness the block author can not grind. `commitment` is the
+		/// hash of `(sender, secret)`, the secret is revealed with `reveal_breed` in a later block.
+		/// The deposit is forfeited if the secret is not revealed within `RevealPeriod` blocks. The
+		/// parents are taken off sale and off the sire market, and stay with the sender until then.
+		#[weight = T::WeightInfo::commit_breed()]
+		pub fn commit_breed(origin, kitty_id_1: T::KittyIndex, kitty_id_2: T::KittyIndex, commitment: T::Hash) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(!BreedCommits::<T>::contains_key((sender.clone(), commitment)), Error::<T>::CommitExists);
+			// Fail early, the parents are checked again on reveal.
+			Self::check_breed(&sender, &sender, kitty_id_1, kitty_id_2)?;
+
+			let now = frame_system::Module::<T>::block_number();
+			let expiry = now.saturating_add(T::RevealPeriod::get());
+			Self::ensure_schedulable(CommitsExpiringAt::<T>::decode_len(expiry))?;
+			for kitty_id in &[kitty_id_1, kitty_id_2] {
+				ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+				if let Some(loan) = Self::loan(kitty_id) {
+					ensure!(loan.end > expiry, Error::<T>::LoanEndsBeforeReveal);
+				}
+			}
+
+			let deposit = T::BreedCommitDeposit::get();
+			T::Currency::reserve(&sender, deposit).map_err(|_| Error::<T>::InsufficientCommitDeposit)?;
+
+			for kitty_id in &[kitty_id_1, kitty_id_2] {
+				KittyPrices::<T>::remove(kitty_id);
+				SireListings::<T>::remove(kitty_id);
+				CommittedKitties::<T>::insert(kitty_id, (sender.clone(), commitment));
+			}
+			BreedCommits::<T>::insert((sender.clone(), commitment), BreedCommit {
+				parents: (kitty_id_1, kitty_id_2),
+				deposit,
+				block: now,
+				expiry,
+			});
+			CommitsExpiringAt::<T>::append(expiry, (sender.clone(), commitment));
+			NextCommitSeed::<T>::put((now.saturating_add(One::one()), expiry));
+
+			Self::deposit_event(RawEvent::BreedCommitted(sender, commitment, expiry));
+			Ok(())
+		}
+
+		/// Reveals the secret of a breeding commitment and breeds the kitties. The DNA is derived from
+		/// the secret and the seed drawn at the start of the block after the commitment, so it is the
+		/// same in whichever block the secret is revealed. The deposit is returned.
+		#[weight = T::WeightInfo::reveal_breed()]
+		#[transactional]
+		pub fn reveal_breed(origin, secret: [u8; 32]) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let commitment = T::Hashing::hash_of(&(&sender, secret));
+			let commit = Self::breed_commit((sender.clone(), commitment)).ok_or(Error::<T>::CommitNotFound)?;
+			let now = frame_system::Module::<T>::block_number();
+			ensure!(now > commit.block, Error::<T>::RevealTooEarly);
+			ensure!(now <= commit.expiry, Error::<T>::Expired);
+
+			BreedCommits::<T>::remove((sender.clone(), commitment));
+			T::Currency::unreserve(&sender, commit.deposit);
+			let (kitty_id_1, kitty_id_2) = commit.parents;
+			CommittedKitties::<T>::remove(kitty_id_1);
+			CommittedKitties::<T>::remove(kitty_id_2);
+
+			let (kitty1, kitty2) = Self::check_breed(&sender, &sender, kitty_id_1, kitty_id_2)?;
+			let seed = Self::commit_seed(commit.expiry).ok_or(Error::<T>::RevealTooEarly)?;
+			let selector = (secret, seed, &sender).using_encoded(blake2_128);
+			let new_kitty_id = Self::breed_child(&sender, kitty_id_1, kitty1, kitty_id_2, kitty2, selector)?;
+
+			Self::deposit_event(RawEvent::Created(sender, new_kitty_id));
+			Ok(())
+		}
+
+		/// Transfers all the kitties to `to`. Either every kitty is transferred or none is.
+		#[weight = T::WeightInfo::transfer().saturating_mul(kitty_ids.len() as Weight)]
+		#[transactional]
+		pub fn transfer_many(origin, to: T::AccountId, kitty_ids: Vec<T::KittyIndex>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(kitty_ids.len() <= T::MaxBatchSize::get() as usize, Error::<T>::BatchTooLarge);
+
+			for kitty_id in kitty_ids {
+				Self::ensure_transferable(&sender, kitty_id)?;
+				Self::do_transfer(&sender, &to, kitty_id);
+
+				Self::deposit_event(RawEvent::Transferred(sender.clone(), to.clone(), kitty_id));
+			}
+			Ok(())
+		}
+
+		/// Sets or clears the price of all the kitties. Either every price is updated or none is.
+		#[weight = T::WeightInfo::ask().saturating_mul(asks.len() as Weight)]
+		#[transactional]
+		pub fn ask_many(origin, asks: Vec<(T::KittyIndex, Option<BalanceOf<T>>)>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(asks.len() <= T::MaxBatchSize::get() as usize, Error::<T>::BatchTooLarge);
+
+			for (kitty_id, price) in asks {
+				Self::ensure_transferable(&sender, kitty_id)?;
+				Self::set_price(kitty_id, price);
+
+				Self::deposit_event(RawEvent::Ask(sender.clone(), kitty_id, price));
+			}
+			Ok(())
+		}
+
+		/// Breeds every pair in turn, so a kitty can only appear in one pair while it has a cooldown.
+		/// Either every pair is bred or none is.
+		#[weight = T::WeightInfo::breed().saturating_mul(pairs.len() as Weight)]
+		#[transactional]
+		pub fn breed_many(origin, pairs: Vec<(T::KittyIndex, T::KittyIndex)>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(pairs.len() <= T::MaxBatchSize::get() as usize, Error::<T>::BatchTooLarge);
+
+			for (kitty_id_1, kitty_id_2) in pairs {
+				let new_kitty_id = Self::do_breed(&sender, kitty_id_1, kitty_id_2)?;
+
+				Self::deposit_event(RawEvent::Created(sender.clone(), new_kitty_id));
+			}
+			Ok(())
+		}
+
+		#[weight = T::DbWeight::get().writes(1)]
+		pub fn set_mutation_rate(origin, rate: Permill) -> dispatch::DispatchResult {
+			ensure_root(origin)?;
+			MutationRate::put(rate);
+			Ok(())
+		}
+
+		#[weight = T::DbWeight::get().writes(1)]
+		pub fn set_breeding_cooldown(origin, cooldown: T::BlockNumber) -> dispatch::DispatchResult {
+			ensure_root(origin)?;
+			BreedingCooldown::<T>::put(cooldown);
+			Ok(())
+		}
+
+		/// Creates a collection owned by the sender, reserving `CollectionDeposit`. The supply cap can
+		/// not be changed afterwards.
+		#[weight = T::WeightInfo::create_collection()]
+		pub fn create_collection(
+			origin,
+			max_supply: Option<u32>,
+			mint_price: BalanceOf<T>,
+			allowlist_only: bool,
+			cross_breeding: bool,
+		) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let collection_id = Self::next_collection_id();
+			ensure!(collection_id != T::CollectionId::max_value(), Error::<T>::CollectionIdOverflow);
+			let deposit = T::CollectionDeposit::get();
+			T::Currency::reserve(&sender, deposit).map_err(|_| Error::<T>::InsufficientDeposit)?;
+
+			CollectionDeposits::<T>::insert(collection_id, deposit);
+			Collections::<T>::insert(collection_id, Collection {
+				owner: sender.clone(),
+				max_supply,
+				supply: 0,
+				mint_price,
+				allowlist_only,
+				cross_breeding,
+			});
+			NextCollectionId::<T>::put(collection_id + One::one());
+
+			Self::deposit_event(RawEvent::CollectionCreated(sender, collection_id));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::update_collection()]
+		pub fn update_collection(
+			origin,
+			collection_id: T::CollectionId,
+			mint_price: BalanceOf<T>,
+			allowlist_only: bool,
+			cross_breeding: bool,
+		) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Collections::<T>::try_mutate(collection_id, |collection| -> dispatch::DispatchResult {
+				let collection = collection.as_mut().ok_or(Error::<T>::CollectionNotFound)?;
+				ensure!(collection.owner == sender, Error::<T>::NotCollectionOwner);
+				collection.mint_price = mint_price;
+				collection.allowlist_only = allowlist_only;
+				collection.cross_breeding = cross_breeding;
+				Ok(())
+			})?;
+
+			Self::deposit_event(RawEvent::CollectionUpdated(collection_id));
+			Ok(())
+		}
+
+		/// Adds `accounts` to the allowlist of a collection, or removes them if `allowed` is false.
+		#[weight = T::WeightInfo::set_allowlist(accounts.len() as u32)]
+		pub fn set_allowlist(origin, collection_id: T::CollectionId, accounts: Vec<T::AccountId>, allowed: bool) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(accounts.len() as u32 <= T::MaxBatchSize::get(), Error::<T>::BatchTooLarge);
+			let collection = Self::collection(collection_id).ok_or(Error::<T>::CollectionNotFound)?;
+			ensure!(collection.owner == sender, Error::<T>::NotCollectionOwner);
+
+			let mut size = Self::collection_allowlist_size(collection_id);
+			for account in accounts {
+				if allowed != Self::is_allowlisted(collection_id, &account) {
+					if allowed {
+						CollectionAllowlist::<T>::insert(collection_id, &account, true);
+						size = size.saturating_add(1);
+					} else {
+						CollectionAllowlist::<T>::remove(collection_id, &account);
+						size = size.saturating_sub(1);
+					}
+				}
+				Self::deposit_event(RawEvent::AllowlistSet(collection_id, account, allowed));
+			}
+			CollectionAllowlistSize::<T>::insert(collection_id, size);
+			Ok(())
+		}
+
+		/// Destroys a collection without kitties and an empty allowlist, returning its deposit to the owner.
+		#[weight = T::WeightInfo::destroy_collection()]
+		pub fn destroy_collection(origin, collection_id: T::CollectionId) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let collection = Self::collection(collection_id).ok_or(Error::<T>::CollectionNotFound)?;
+			ensure!(collection.owner == sender, Error::<T>::NotCollectionOwner);
+			let deposit = Self::collection_deposit(collection_id).ok_or(Error::<T>::PermanentCollection)?;
+			ensure!(Self::collection_kitties(collection_id) == 0, Error::<T>::CollectionNotEmpty);
+			ensure!(Self::collection_allowlist_size(collection_id) == 0, Error::<T>::AllowlistNotEmpty);
+
+			Collections::<T>::remove(collection_id);
+			CollectionDeposits::<T>::remove(collection_id);
+			CollectionKitties::<T>::remove(collection_id);
+			CollectionAllowlistSize::<T>::remove(collection_id);
+			T::Currency::unreserve(&sender, deposit);
+
+			Self::deposit_event(RawEvent::CollectionDestroyed(collection_id));
+			Ok(())
+		}
+
+		/// Changes the deposit of new kitties, existing kitties keep the deposit they were minted with.
+		#[weight = T::DbWeight::get().writes(1)]
+		pub fn set_mint_deposit(origin, deposit: BalanceOf<T>) -> dispatch::DispatchResult {
+			ensure_root(origin)?;
+			MintDeposit::<T>::put(deposit);
+			Ok(())
+		}
+
+		#[weight = T::DbWeight::get().writes(1)]
+		pub fn set_max_kitties_per_account(origin, max: u32) -> dispatch::DispatchResult {
+			ensure_root(origin)?;
+			MaxKittiesPerAccount::put(max);
+			Ok(())
+		}
+
+		#[weight = T::DbWeight::get().writes(1)]
+		pub fn set_marketplace_fee(origin, fee: Permill) -> dispatch::DispatchResult {
+			ensure_root(origin)?;
+			MarketplaceFee::put(fee);
+			Ok(())
+		}
+
+		/// Ends the loans due in this block, so the kitties are back with their owners before any
+		/// extrinsic, and draws the reveal seed of the commitments made in the previous block. The
+		/// auctions, offers and commitments settled in `on_finalize` are accounted for here up front.
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let loans = LoansEndingAt::<T>::take(n);
+			let loans_count = loans.len() as Weight;
+			for kitty_id in loans {
+				Self::end_loan(kitty_id, n);
+			}
+
+			let auctions = AuctionsEndingAt::<T>::decode_len(n).unwrap_or(0) as Weight;
+			let offers = OffersExpiringAt::<T>::decode_len(n).unwrap_or(0) as Weight;
+			let commits = CommitsExpiringAt::<T>::decode_len(n).unwrap_or(0) as Weight;
+			let seed = Self::draw_commit_seed(n) as Weight;
+			T::DbWeight::get().reads_writes(
+				5 + 4 * loans_count + 8 * auctions + 3 * offers + 2 * commits,
+				5 + 5 * loans_count + 10 * auctions + 4 * offers + 4 * commits + 2 * seed,
+			)
+		}
+
+		fn on_finalize(n: T::BlockNumber) {
+			for kitty_id in AuctionsEndingAt::<T>::take(n) {
+				Self::settle_auction(kitty_id, n);
+			}
+			for (kitty_id, buyer) in OffersExpiringAt::<T>::take(n) {
+				Self::expire_offer(kitty_id, buyer, n);
+			}
+			for (breeder, commitment) in CommitsExpiringAt::<T>::take(n) {
+				Self::forfeit_commit(breeder, commitment, n);
+			}
+			CommitSeeds::<T>::remove(n);
+		}
+	}
+}
+
+impl<T: Config> Module<T> {
+	/// Account of the pallet, it collects the marketplace fees.
+	pub fn account_id() -> T::AccountId {
+		T::ModuleId::get().into_account()
+	}
+
+	/// Sets or clears the price of a kitty and bumps its nonce, so buys agreed on the previous price fail.
+	fn set_price(kitty_id: T::KittyIndex, price: Option<BalanceOf<T>>) {
+		if let Some(price) = price {
+			KittyPrices::<T>::insert(kitty_id, price);
+		} else {
+			KittyPrices::<T>::remove(kitty_id);
+		}
+		KittyPriceNonces::<T>::mutate(kitty_id, |nonce| *nonce = nonce.wrapping_add(1));
+	}
+
+	/// Draws the seed of the commitments made in the previous block, true if there were any.
+	fn draw_commit_seed(n: T::BlockNumber) -> bool {
+		match Self::next_commit_seed() {
+			Some((block, expiry)) if block <= n => {
+				NextCommitSeed::<T>::kill();
+				CommitSeeds::<T>::insert(expiry, T::Randomness::random(&(b"kitties/reveal", n).encode()));
+				true
+			},
+			_ => false,
+		}
+	}
+
+	fn random_value(sender: &T::AccountId) -> [u8; 16] {
+		let payload = (
+			T::Randomness::random_seed(),
+			sender,
+			frame_system::Module::<T>::extrinsic_index(),
+			frame_system::Module::<T>::block_number(),
+		);
+		payload.using_encoded(blake2_128)
+	}
+
+	fn is_owner(who: &T::AccountId, kitty_id: T::KittyIndex) -> bool {
+		OwnedKitties::<T>::contains_key((who.clone(), Some(kitty_id)))
+	}
+
+	/// Checks that `who` owns the kitty and that it is neither locked in an auction nor lent out.
+	fn ensure_transferable(who: &T::AccountId, kitty_id: T::KittyIndex) -> dispatch::DispatchResult {
+		ensure!(Self::is_owner(who, kitty_id), Error::<T>::NotOwner);
+		ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+		ensure!(!Loans::<T>::contains_key(kitty_id), Error::<T>::KittyOnLoan);
+		ensure!(!CommittedKitties::<T>::contains_key(kitty_id), Error::<T>::KittyCommitted);
+		Ok(())
+	}
+
+	/// The account that can use the kitty: the borrower while it is lent out, the owner otherwise.
+	pub fn custodian(kitty_id: T::KittyIndex) -> Option<T::AccountId> {
+		match Self::loan(kitty_id) {
+			Some(loan) => Some(loan.borrower),
+			None => Self::kitty_owner(kitty_id),
+		}
+	}
+
+	fn is_custodian(who: &T::AccountId, kitty_id: T::KittyIndex) -> bool {
+		Self::custodian(kitty_id).as_ref() == Some(who)
+	}
+
+	fn release_metadata_deposit(kitty_id: T::KittyIndex) {
+		if let Some((depositor, deposit)) = MetadataDeposits::<T>::take(kitty_id) {
+			T::Currency::unreserve(&depositor, deposit);
+		}
+	}
+
+	fn end_loan(kitty_id: T::KittyIndex, now: T::BlockNumber) {
+		let loan = match Self::loan(kitty_id) {
+			Some(loan) if loan.end == now => loan,
+			_ => return,
+		};
+		Loans::<T>::remove(kitty_id);
+		BorrowedKittiesList::<T>::remove(&loan.borrower, kitty_id);
+
+		if let Some(owner) = Self::kitty_owner(kitty_id) {
+			Self::deposit_event(RawEvent::LoanEnded(owner, loan.borrower, kitty_id));
+		}
+	}
+
+	fn next_kitty_id() -> Result<T::KittyIndex, Error<T>> {
+		let kitty_id = Self::kitties_count();
+		if kitty_id == T::KittyIndex::max_value() {
+			return Err(Error::<T>::KittiesCountOverflow);
+		}
+		Ok(kitty_id)
+	}
+
+	/// The id of the next kitty if `owner` can mint it in `collection_id`. The per-account limit only applies to minting, transfers are not capped.
+	fn ensure_can_mint(owner: &T::AccountId, collection_id: T::CollectionId) -> Result<T::KittyIndex, Error<T>> {
+		let kitty_id = Self::next_kitty_id()?;
+		let collection = Self::collection(collection_id).ok_or(Error::<T>::CollectionNotFound)?;
+		if collection.allowlist_only && collection.owner != *owner && !Self::is_allowlisted(collection_id, owner) {
+			return Err(Error::<T>::NotAllowlisted);
+		}
+		if collection.max_supply.map_or(false, |max| collection.supply >= max) {
+			return Err(Error::<T>::CollectionFull);
+		}
+		let max = Self::max_kitties_per_account();
+		if max != 0 && Self::owned_kitties_count(owner) >= max {
+			return Err(Error::<T>::TooManyKitties);
+		}
+		if !T::Currency::can_reserve(owner, Self::mint_deposit()) {
+			return Err(Error::<T>::InsufficientDeposit);
+		}
+		Ok(kitty_id)
+	}
+
+	/// Pays the mint price of a collection to its owner, who mints for free.
+	fn pay_mint_price(minter: &T::AccountId, collection_id: T::CollectionId) -> dispatch::DispatchResult {
+		let collection = Self::collection(collection_id).ok_or(Error::<T>::CollectionNotFound)?;
+		if collection.owner != *minter && !collection.mint_price.is_zero() {
+			T::Currency::transfer(minter, &collection.owner, collection.mint_price, ExistenceRequirement::KeepAlive)?;
+		}
+		Ok(())
+	}
+
+	fn insert_kitty(
+		owner: &T::AccountId,
+		kitty_id: T::KittyIndex,
+		collection_id: T::CollectionId,
+		kitty: KittyOf<T>,
+	) -> Result<(), Error<T>> {
+		let deposit = Self::mint_deposit();
+		T::Currency::reserve(owner, deposit).map_err(|_| Error::<T>::InsufficientDeposit)?;
+		if !deposit.is_zero() {
+			KittyDeposits::<T>::insert(kitty_id, (owner.clone(), deposit));
+		}
+
+		Kitties::<T>::insert(kitty_id, kitty);
+		KittiesCount::<T>::put(kitty_id + One::one());
+		KittyOwners::<T>::insert(kitty_id, owner);
+		KittyBreeders::<T>::insert(kitty_id, owner);
+		OwnedKittiesList::<T>::append(owner, kitty_id);
+		KittyCollection::<T>::insert(kitty_id, collection_id);
+		CollectionKitties::<T>::mutate(collection_id, |count| *count = count.saturating_add(1));
+		Collections::<T>::mutate(collection_id, |collection| if let Some(collection) = collection {
+			collection.supply = collection.supply.saturating_add(1);
+		});
+		Ok(())
+	}
+
+	/// The breeder and its share of a resale of the kitty by `seller`. Nothing is due when the breeder sells.
+	fn royalty_of(kitty_id: T::KittyIndex, seller: &T::AccountId, price: BalanceOf<T>) -> Option<(T::AccountId, BalanceOf<T>)> {
+		let breeder = Self::kitty_breeder(kitty_id).filter(|breeder| breeder != seller)?;
+		// Capped again in case `MaxRoyalty` was lowered after the royalty was set.
+		let royalty = Self::kitty_royalty(kitty_id).min(T::MaxRoyalty::get()) * price;
+		if royalty.is_zero() {
+			return None;
+		}
+		Some((breeder, royalty))
+	}
+
+	/// Pays `amount` reserved on `buyer` for the kitty of `seller`, minus the royalty that goes to the breeder.
+	/// Nothing is paid if either payment fails.
+	#[transactional]
+	fn pay_reserved(buyer: &T::AccountId, seller: &T::AccountId, kitty_id: T::KittyIndex, amount: BalanceOf<T>) -> dispatch::DispatchResult {
+		let mut proceeds = amount;
+		if let Some((breeder, royalty)) = Self::royalty_of(kitty_id, seller, amount) {
+			T::Currency::repatriate_reserved(buyer, &breeder, royalty, BalanceStatus::Free)?;
+			proceeds -= royalty;
+			Self::deposit_event(RawEvent::RoyaltyPaid(breeder, kitty_id, royalty));
+		}
+		T::Currency::repatriate_reserved(buyer, seller, proceeds, BalanceStatus::Free)?;
+		Ok(())
+	}
+
+	/// Whether `who` can move the kitty of `owner`.
+	fn is_approved(who: &T::AccountId, owner: &T::AccountId, kitty_id: T::KittyIndex) -> bool {
+		who == owner
+			|| Self::kitty_approval(kitty_id).as_ref() == Some(who)
+			|| Self::is_approved_for_all((owner.clone(), who.clone()))
+	}
+
+	fn do_transfer(from: &T::AccountId, to: &T::AccountId, kitty_id: T::KittyIndex) {
+		OwnedKittiesList::<T>::remove(from, kitty_id);
+		OwnedKittiesList::<T>::append(to, kitty_id);
+		KittyOwners::<T>::insert(kitty_id, to);
+		// The price, sire listings, approvals and loan offers are granted by the previous owner.
+		Self::set_price(kitty_id, None);
+		SireListings::<T>::remove(kitty_id);
+		KittyApprovals::<T>::remove(kitty_id);
+		LoanOffers::<T>::remove(kitty_id);
+	}
+
+	/// Removes a kitty and everything attached to it. Kitty ids are never reused, so children of a burned kitty keep its id as parent.
+	fn do_burn(owner: &T::AccountId, kitty_id: T::KittyIndex) {
+		for buyer in KittyOffers::<T>::take(kitty_id) {
+			if let Some(offer) = Offers::<T>::take((kitty_id, buyer.clone())) {
+				T::Currency::unreserve(&buyer, offer.amount);
+				Self::unschedule_offer(offer.expiry, kitty_id, &buyer);
+			}
+		}
+
+		OwnedKittiesList::<T>::remove(owner, kitty_id);
+		Kitties::<T>::remove(kitty_id);
+		KittyOwners::<T>::remove(kitty_id);
+		KittyPrices::<T>::remove(kitty_id);
+		KittyPriceNonces::<T>::remove(kitty_id);
+		SireListings::<T>::remove(kitty_id);
+		KittyApprovals::<T>::remove(kitty_id);
+		LoanOffers::<T>::remove(kitty_id);
+		KittyBreeders::<T>::remove(kitty_id);
+		KittyRoyalties::<T>::remove(kitty_id);
+		KittyCooldowns::<T>::remove(kitty_id);
+		let collection_id = KittyCollection::<T>::take(kitty_id);
+		CollectionKitties::<T>::mutate(collection_id, |count| *count = count.saturating_sub(1));
+
+		if let Some((depositor, deposit)) = KittyDeposits::<T>::take(kitty_id) {
+			T::Currency::unreserve(&depositor, deposit);
+		}
+		Metadata::<T>::remove(kitty_id);
+		Self::release_metadata_deposit(kitty_id);
+	}
+
+	fn do_breed(sender: &T::AccountId, kitty_id_1: T::KittyIndex, kitty_id_2: T::KittyIndex) -> Result<T::KittyIndex, DispatchError> {
+		let (kitty1, kitty2) = Self::check_breed(sender, sender, kitty_id_1, kitty_id_2)?;
+		Self::breed_child(sender, kitty_id_1, kitty1, kitty_id_2, kitty2, Self::random_value(sender))
+	}
+
+	/// Checks that `sender` can breed `kitty_id_1` with `kitty_id_2`, each in the custody of `sender` and `sire_owner`.
+	fn check_breed(
+		sender: &T::AccountId,
+		sire_owner: &T::AccountId,
+		kitty_id_1: T::KittyIndex,
+		kitty_id_2: T::KittyIndex,
+	) -> Result<(KittyOf<T>, KittyOf<T>), DispatchError> {
+		let kitty1 = Self::kitty(kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
+		let kitty2 = Self::kitty(kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
+		ensure!(kitty_id_1 != kitty_id_2, Error::<T>::RequireDifferentParent);
+		ensure!(Self::is_custodian(sender, kitty_id_1), Error::<T>::NotOwner);
+		ensure!(Self::is_custodian(sire_owner, kitty_id_2), Error::<T>::NotOwner);
+		ensure!(
+			!CommittedKitties::<T>::contains_key(kitty_id_1) && !CommittedKitties::<T>::contains_key(kitty_id_2),
+			Error::<T>::KittyCommitted
+		);
+
+		let now = frame_system::Module::<T>::block_number();
+		ensure!(now >= Self::kitty_cooldown(kitty_id_1), Error::<T>::KittyOnCooldown);
+		ensure!(now >= Self::kitty_cooldown(kitty_id_2), Error::<T>::KittyOnCooldown);
+		ensure!(!kitty1.is_related(kitty_id_1, &kitty2, kitty_id_2), Error::<T>::ParentsAreRelated);
+
+		let collection_id = Self::kitty_collection(kitty_id_1);
+		let sire_collection_id = Self::kitty_collection(kitty_id_2);
+		if collection_id != sire_collection_id {
+			ensure!(
+				Self::allows_cross_breeding(collection_id) && Self::allows_cross_breeding(sire_collection_id),
+				Error::<T>::CrossBreedingNotAllowed
+			);
+		}
+		Self::ensure_can_mint(sender, collection_id)?;
+
+		Ok((kitty1, kitty2))
+	}
+
+	fn breed_child(
+		sender: &T::AccountId,
+		kitty_id_1: T::KittyIndex,
+		kitty1: KittyOf<T>,
+		kitty_id_2: T::KittyIndex,
+		kitty2: KittyOf<T>,
+		selector: [u8; 16],
+	) -> Result<T::KittyIndex, DispatchError> {
+		let now = frame_system::Module::<T>::block_number();
+		let kitty_id = Self::next_kitty_id()?;
+
+		let mutation = blake2_128(&selector);
+
+		let new_dna = genetics::inherit(&kitty1.dna, &kitty2.dna, &selector, &mutation, Self::mutation_rate());
+
+		let collection_id = Self::kitty_collection(kitty_id_1);
+		Self::pay_mint_price(sender, collection_id)?;
+		Self::insert_kitty(sender, kitty_id, collection_id, Kitty {
+			dna: new_dna,
+			parents: Some((kitty_id_1, kitty_id_2)),
+			generation: kitty1.generation.max(kitty2.generation).saturating_add(1),
+			birth: now,
+		})?;
+
+		KittyCooldowns::<T>::insert(kitty_id_1, now.saturating_add(Self::cooldown_of(&kitty1)));
+		KittyCooldowns::<T>::insert(kitty_id_2, now.saturating_add(Self::cooldown_of(&kitty2)));
+
+		Ok(kitty_id)
+	}
+
+	fn allows_cross_breeding(collection_id: T::CollectionId) -> bool {
+		Self::collection(collection_id).map_or(false, |collection| collection.cross_breeding)
+	}
+
+	fn cooldown_of(kitty: &KittyOf<T>) -> T::BlockNumber {
+		Self::breeding_cooldown().saturating_mul((u32::from(kitty.generation) + 1).into())
+	}
+
+	fn kitty_info(owner: &T::AccountId, kitty_id: T::KittyIndex) -> Option<KittyInfo<T::AccountId, T::KittyIndex, BalanceOf<T>>> {
+		Self::kitty(kitty_id).map(|kitty| KittyInfo {
+			id: kitty_id,
+			dna: kitty.dna,
+			price: Self::kitty_price(kitty_id),
+			price_nonce: Self::price_nonce(kitty_id),
+			owner: owner.clone(),
+		})
+	}
+
+	/// A page of at most `limit` kitties of `account`, capped by `MAX_PAGE_SIZE`. An unknown cursor yields an empty page.
+	pub fn owned_kitties_page(
+		account: &T::AccountId,
+		cursor: Option<T::KittyIndex>,
+		limit: u32,
+	) -> KittiesPage<T::AccountId, T::KittyIndex, BalanceOf<T>> {
+		let page_size = limit.min(MAX_PAGE_SIZE) as usize;
+		let iter = match cursor {
+			Some(kitty_id) => OwnedKittiesList::<T>::iter_from(account, kitty_id),
+			None => OwnedKittiesList::<T>::iter(account),
+		};
+		// One more than the page size, the extra kitty is the cursor of the next page.
+		let mut ids: Vec<_> = iter.take(page_size + 1).collect();
+		let next = if ids.len() > page_size { ids.pop() } else { None };
+
+		KittiesPage {
+			kitties: ids.into_iter().filter_map(|kitty_id| Self::kitty_info(account, kitty_id)).collect(),
+			next,
+			total: u64::from(Self::owned_kitties_count(account)),
+		}
+	}
+
+	pub fn kitty_traits(kitty_id: T::KittyIndex) -> Option<KittyTraits> {
+		Self::kitty(kitty_id).map(|kitty| genetics::decode(&kitty.dna))
+	}
+
+	/// The price of a Dutch auction falls linearly from the start price to the reserve price.
+	fn dutch_price(auction: &AuctionOf<T>, now: T::BlockNumber) -> BalanceOf<T> {
+		if now >= auction.end {
+			return auction.reserve_price;
+		}
+		let elapsed = Perbill::from_rational_approximation(now - auction.start, auction.end - auction.start);
+		auction.start_price - elapsed * (auction.start_price - auction.reserve_price)
+	}
+
+	fn settle_auction(kitty_id: T::KittyIndex, now: T::BlockNumber) {
+		let auction = match Self::auction(kitty_id) {
+			Some(auction) if auction.end == now => auction,
+			// Cancelled, or a Dutch auction that was won before its end.
+			_ => return,
+		};
+		Auctions::<T>::remove(kitty_id);
+
+		match auction.bid {
+			Some((bidder, amount)) => {
+				if amount >= auction.reserve_price && Self::pay_reserved(&bidder, &auction.seller, kitty_id, amount).is_ok() {
+					Self::do_transfer(&auction.seller, &bidder, kitty_id);
+
+					Self::deposit_event(RawEvent::AuctionSettled(auction.seller, bidder, kitty_id, amount));
+				} else {
+					// Below the reserve price, or the bid could not be paid out.
+					T::Currency::unreserve(&bidder, amount);
+
+					Self::deposit_event(RawEvent::AuctionFailed(auction.seller, kitty_id));
+				}
+			},
+			None => Self::deposit_event(RawEvent::AuctionFailed(auction.seller, kitty_id)),
+		}
+	}
+
+	fn forfeit_commit(breeder: T::AccountId, commitment: T::Hash, now: T::BlockNumber) {
+		match Self::breed_commit((breeder.clone(), commitment)) {
+			Some(ref commit) if commit.expiry == now => (),
+			// Revealed, or replaced by a new commitment with the same secret.
+			_ => return,
+		}
+		if let Some(commit) = BreedCommits::<T>::take((breeder.clone(), commitment)) {
+			let _ = T::Currency::slash_reserved(&breeder, commit.deposit);
+			CommittedKitties::<T>::remove(commit.parents.0);
+			CommittedKitties::<T>::remove(commit.parents.1);
+
+			Self::deposit_event(RawEvent::BreedCommitForfeited(breeder, commitment, commit.deposit));
+		}
+	}
+
+	fn remove_offer(kitty_id: T::KittyIndex, buyer: &T::AccountId) -> Option<Offer<BalanceOf<T>, T::BlockNumber>> {
+		let offer = Offers::<T>::take((kitty_id, buyer.clone()))?;
+		KittyOffers::<T>::mutate(kitty_id, |offers| offers.retain(|who| who != buyer));
+		Self::unschedule_offer(offer.expiry, kitty_id, buyer);
+		Some(offer)
+	}
+
+	/// Fails if `MaxScheduledPerBlock` entries of a kind already end in a block, `scheduled` is the
+	/// length of its list.
+	fn ensure_schedulable(scheduled: Option<usize>) -> Result<(), Error<T>> {
+		ensure!((scheduled.unwrap_or(0) as u32) < T::MaxScheduledPerBlock::get(), Error::<T>::TooManyScheduled);
+		Ok(())
+	}
+
+	/// Takes an offer off the sweep of its expiry block, so replacing an offer does not pile up entries.
+	fn unschedule_offer(expiry: T::BlockNumber, kitty_id: T::KittyIndex, buyer: &T::AccountId) {
+		OffersExpiringAt::<T>::mutate_exists(expiry, |offers| {
+			if let Some(list) = offers {
+				list.retain(|(id, who)| *id != kitty_id || who != buyer);
+				if list.is_empty() {
+					*offers = None;
+				}
+			}
+		});
+	}
+
+	fn expire_offer(kitty_id: T::KittyIndex, buyer: T::AccountId, now: T::BlockNumber) {
+		match Self::offer((kitty_id, buyer.clone())) {
+			Some(ref offer) if offer.expiry == now => (),
+			// Entries leave the sweep together with their offers, this only guards against a mismatch.
+			_ => return,
+		}
+		if let Some(offer) = Self::remove_offer(kitty_id, &buyer) {
+			T::Currency::unreserve(&buyer, offer.amount);
+
+			Self::deposit_event(RawEvent::OfferExpired(buyer, kitty_id));
+		}
+	}
+}
@@ -0,0 +1,326 @@
+//! A double linked list per key, kept in a storage map.
+
+use codec::{Encode, Decode};
+use frame_support::{StorageMap, Parameter};
+use sp_runtime::{traits::Member, RuntimeDebug};
+use sp_std::{prelude::*, marker::PhantomData};
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct LinkedItem<Item> {
+	pub prev: Option<Item>,
+	pub next: Option<Item>,
+}
+
+/// A double linked list per key, stored as one `LinkedItem` per value in `Storage`.
+///
+/// The head of a list is stored under `(key, None)`, its `next` is the first value and its
+/// `prev` the last one. `LenStorage` keeps the number of values of every list.
+pub struct LinkedList<Storage, LenStorage, Key, Item>(PhantomData<(Storage, LenStorage, Key, Item)>);
+
+impl<Storage, LenStorage, Key, Value> LinkedList<Storage, LenStorage, Key, Value> where
+	Value: Parameter + Member + Copy,
+	Key: Parameter,
+	Storage: StorageMap<(Key, Option<Value>), LinkedItem<Value>, Query = Option<LinkedItem<Value>>>,
+	LenStorage: StorageMap<Key, u32, Query = u32>,
+{
+	fn read_head(key: &Key) -> LinkedItem<Value> {
+		Self::read(key, None)
+	}
+
+	fn read(key: &Key, value: Option<Value>) -> LinkedItem<Value> {
+		Storage::get(&(key.clone(), value)).unwrap_or_else(|| LinkedItem {
+			prev: None,
+			next: None,
+		})
+	}
+
+	fn write(key: &Key, value: Option<Value>, item: LinkedItem<Value>) {
+		Storage::insert(&(key.clone(), value), item);
+	}
+
+	/// Links `value` between `prev` and `next`, `None` stands for the head.
+	fn link(key: &Key, prev: Option<Value>, next: Option<Value>, value: Value) {
+		let mut prev_item = Self::read(key, prev);
+		prev_item.next = Some(value);
+		Self::write(key, prev, prev_item);
+
+		// Read after writing `prev`, both are the head if the list is empty.
+		let mut next_item = Self::read(key, next);
+		next_item.prev = Some(value);
+		Self::write(key, next, next_item);
+
+		Self::write(key, Some(value), LinkedItem { prev, next });
+		LenStorage::mutate(key, |len| *len += 1);
+	}
+
+	/// Number of values in the list.
+	pub fn len(key: &Key) -> u32 {
+		LenStorage::get(key)
+	}
+
+	pub fn is_empty(key: &Key) -> bool {
+		Self::len(key) == 0
+	}
+
+	pub fn contains(key: &Key, value: Value) -> bool {
+		Storage::contains_key(&(key.clone(), Some(value)))
+	}
+
+	pub fn front(key: &Key) -> Option<Value> {
+		Self::read_head(key).next
+	}
+
+	pub fn back(key: &Key) -> Option<Value> {
+		Self::read_head(key).prev
+	}
+
+	/// Adds `value` at the end of the list. Returns `false` and leaves the list unchanged if it already contains `value`.
+	pub fn append(key: &Key, value: Value) -> bool {
+		if Self::contains(key, value) {
+			return false;
+		}
+		let head = Self::read_head(key);
+		Self::link(key, head.prev, None, value);
+		true
+	}
+
+	/// Adds `value` right after `anchor`. Returns `false` if `anchor` is not in the list or `value` already is.
+	pub fn insert_after(key: &Key, anchor: Value, value: Value) -> bool {
+		if Self::contains(key, value) {
+			return false;
+		}
+		match Storage::get(&(key.clone(), Some(anchor))) {
+			Some(item) => {
+				Self::link(key, Some(anchor), item.next, value);
+				true
+			},
+			None => false,
+		}
+	}
+
+	/// Adds `value` right before `anchor`. Returns `false` if `anchor` is not in the list or `value` already is.
+	pub fn insert_before(key: &Key, anchor: Value, value: Value) -> bool {
+		if Self::contains(key, value) {
+			return false;
+		}
+		match Storage::get(&(key.clone(), Some(anchor))) {
+			Some(item) => {
+				Self::link(key, item.prev, Some(anchor), value);
+				true
+			},
+			None => false,
+		}
+	}
+
+	/// Removes `value` from the list. Returns `false` if it is not in the list.
+	pub fn remove(key: &Key, value: Value) -> bool {
+		if let Some(item) = Storage::take(&(key.clone(), Some(value))) {
+			let prev = Self::read(key, item.prev);
+			let new_prev = LinkedItem {
+				prev: prev.prev,
+				next: item.next,
+			};
+
+			Self::write(key, item.prev, new_prev);
+
+			let next = Self::read(key, item.next);
+			let new_next = LinkedItem {
+				prev: item.prev,
+				next: next.next,
+			};
+
+			Self::write(key, item.next, new_next);
+
+			LenStorage::mutate(key, |len| *len = len.saturating_sub(1));
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Removes and returns the first value of the list.
+	pub fn pop_front(key: &Key) -> Option<Value> {
+		let value = Self::front(key)?;
+		Self::remove(key, value);
+		Some(value)
+	}
+
+	/// Iterates over the list from the first value. The iterator reads one value from storage per
+	/// step, bound it with `take` to limit the work done.
+	pub fn iter(key: &Key) -> Iter<Storage, Key, Value> {
+		Iter {
+			key: key.clone(),
+			next: Self::front(key),
+			_phantom: PhantomData,
+		}
+	}
+
+	/// Iterates over the list starting at `value`, empty if `value` is not in the list.
+	pub fn iter_from(key: &Key, value: Value) -> Iter<Storage, Key, Value> {
+		Iter {
+			key: key.clone(),
+			next: if Self::contains(key, value) { Some(value) } else { None },
+			_phantom: PhantomData,
+		}
+	}
+
+	/// Removes every value and the head of the list, returning the values in order.
+	pub fn take_all(key: &Key) -> Vec<Value> {
+		let values: Vec<Value> = Self::iter(key).collect();
+		for value in &values {
+			Storage::remove(&(key.clone(), Some(*value)));
+		}
+		Storage::remove(&(key.clone(), None));
+		LenStorage::remove(key);
+		values
+	}
+
+	/// Removes every value and the head of the list.
+	pub fn clear(key: &Key) {
+		Self::take_all(key);
+	}
+}
+
+/// Iterator over the values of a `LinkedList`.
+pub struct Iter<Storage, Key, Value> {
+	key: Key,
+	next: Option<Value>,
+	_phantom: PhantomData<Storage>,
+}
+
+impl<Storage, Key, Value> Iterator for Iter<Storage, Key, Value> where
+	Value: Parameter + Member + Copy,
+	Key: Parameter,
+	Storage: StorageMap<(Key, Option<Value>), LinkedItem<Value>, Query = Option<LinkedItem<Value>>>,
+{
+	type Item = Value;
+
+	fn next(&mut self) -> Option<Value> {
+		let value = self.next.take()?;
+		self.next = Storage::get(&(self.key.clone(), Some(value))).and_then(|item| item.next);
+		Some(value)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::{decl_module, decl_storage};
+
+	pub trait Config: frame_system::Config {}
+	impl Config for Test {}
+
+	decl_module! {
+		pub struct Module<T: Config> for enum Call where origin: T::Origin {}
+	}
+
+	decl_storage! {
+		trait Store for Module<T: Config> as LinkedListTest {
+			pub Items: map hasher(blake2_128_concat) (u64, Option<u32>) => Option<LinkedItem<u32>>;
+			pub ItemsCount: map hasher(blake2_128_concat) u64 => u32;
+		}
+	}
+
+	type List = LinkedList<Items, ItemsCount, u64, u32>;
+
+	fn values(key: u64) -> Vec<u32> {
+		List::iter(&key).collect()
+	}
+
+	#[test]
+	fn append_keeps_order_and_length() {
+		new_test_ext().execute_with(|| {
+			assert!(List::is_empty(&0));
+			assert!(List::append(&0, 1));
+			assert!(List::append(&0, 2));
+			assert!(List::append(&0, 3));
+
+			assert_eq!(values(0), vec![1, 2, 3]);
+			assert_eq!(List::len(&0), 3);
+			assert_eq!(List::front(&0), Some(1));
+			assert_eq!(List::back(&0), Some(3));
+			assert!(List::contains(&0, 2));
+			assert!(!List::contains(&1, 2));
+			assert!(List::is_empty(&1));
+		});
+	}
+
+	#[test]
+	fn duplicate_append_is_rejected() {
+		new_test_ext().execute_with(|| {
+			List::append(&0, 1);
+			List::append(&0, 2);
+
+			assert!(!List::append(&0, 1));
+			assert_eq!(values(0), vec![1, 2]);
+			assert_eq!(List::len(&0), 2);
+		});
+	}
+
+	#[test]
+	fn insert_after_and_before_anchor() {
+		new_test_ext().execute_with(|| {
+			List::append(&0, 1);
+			List::append(&0, 4);
+
+			assert!(List::insert_after(&0, 1, 2));
+			assert!(List::insert_before(&0, 4, 3));
+			assert!(List::insert_before(&0, 1, 0));
+			assert!(List::insert_after(&0, 4, 5));
+			assert_eq!(values(0), vec![0, 1, 2, 3, 4, 5]);
+			assert_eq!(List::len(&0), 6);
+
+			assert!(!List::insert_after(&0, 9, 6));
+			assert!(!List::insert_before(&0, 0, 3));
+		});
+	}
+
+	#[test]
+	fn remove_and_pop_front() {
+		new_test_ext().execute_with(|| {
+			List::append(&0, 1);
+			List::append(&0, 2);
+			List::append(&0, 3);
+
+			assert!(List::remove(&0, 2));
+			assert!(!List::remove(&0, 2));
+			assert_eq!(values(0), vec![1, 3]);
+
+			assert_eq!(List::pop_front(&0), Some(1));
+			assert_eq!(List::pop_front(&0), Some(3));
+			assert_eq!(List::pop_front(&0), None);
+			assert!(List::is_empty(&0));
+		});
+	}
+
+	#[test]
+	fn iteration_can_be_bounded_and_resumed() {
+		new_test_ext().execute_with(|| {
+			for value in 0..10 {
+				List::append(&0, value);
+			}
+
+			assert_eq!(List::iter(&0).take(3).collect::<Vec<_>>(), vec![0, 1, 2]);
+			assert_eq!(List::iter_from(&0, 8).collect::<Vec<_>>(), vec![8, 9]);
+			assert_eq!(List::iter_from(&0, 10).count(), 0);
+		});
+	}
+
+	#[test]
+	fn take_all_clears_list() {
+		new_test_ext().execute_with(|| {
+			List::append(&0, 1);
+			List::append(&0, 2);
+			List::append(&1, 3);
+
+			assert_eq!(List::take_all(&0), vec![1, 2]);
+			assert_eq!(Items::get(&(0, None)), None);
+			assert_eq!(List::len(&0), 0);
+			assert_eq!(values(1), vec![3]);
+
+			List::clear(&1);
+			assert!(List::is_empty(&1));
+		});
+	}
+}
@@ -0,0 +1,139 @@
+use crate::{Module, Config};
+use sp_core::H256;
+use frame_support::{impl_outer_origin, parameter_types, traits::{Get, Randomness}};
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Permill, ModuleId,
+};
+use sp_io::hashing::blake2_256;
+use frame_system as system;
+use std::cell::RefCell;
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+// Configure a mock runtime to test the pallet.
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaxOffersPerKitty: u32 = 16;
+	pub const MaxMemoLength: u32 = 140;
+	pub const MaxBatchSize: u32 = 4;
+	pub const MaxRoyalty: Permill = Permill::from_percent(10);
+	pub const KittiesModuleId: ModuleId = ModuleId(*b"py/kitty");
+	pub const MaxNameLength: u32 = 16;
+	pub const MaxDescriptionLength: u32 = 64;
+	pub const MetadataDepositPerByte: u64 = 1;
+	pub const BreedCommitDeposit: u64 = 10;
+	pub const RevealPeriod: u64 = 10;
+	pub const MaxScheduledPerBlock: u32 = 2;
+	pub const CollectionDeposit: u64 = 5;
+}
+
+thread_local! {
+	static EXISTENTIAL_DEPOSIT: RefCell<u64> = RefCell::new(1);
+}
+
+/// 1 unless a test sets it before building its externalities.
+pub struct ExistentialDeposit;
+impl ExistentialDeposit {
+	pub fn set(value: u64) {
+		EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = value);
+	}
+}
+impl Get<u64> for ExistentialDeposit {
+	fn get() -> u64 {
+		EXISTENTIAL_DEPOSIT.with(|v| *v.borrow())
+	}
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = ();
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = ();
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type Balance = u64;
+	type DustRemoval = ();
+	type Event = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+/// Randomness that only depends on the subject, the DNA of a kitty still varies with the sender and block.
+pub struct TestRandomness;
+impl Randomness<H256> for TestRandomness {
+	fn random(subject: &[u8]) -> H256 {
+		H256::from(blake2_256(subject))
+	}
+}
+
+impl Config for Test {
+	type Event = ();
+	type KittyIndex = u32;
+	type CollectionId = u32;
+	type CollectionDeposit = CollectionDeposit;
+	type Currency = Balances;
+	type Randomness = TestRandomness;
+	type MaxOffersPerKitty = MaxOffersPerKitty;
+	type MaxMemoLength = MaxMemoLength;
+	type MaxBatchSize = MaxBatchSize;
+	type MaxRoyalty = MaxRoyalty;
+	type ModuleId = KittiesModuleId;
+	type MaxNameLength = MaxNameLength;
+	type MaxDescriptionLength = MaxDescriptionLength;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type BreedCommitDeposit = BreedCommitDeposit;
+	type RevealPeriod = RevealPeriod;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type WeightInfo = ();
+}
+
+pub type System = system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
+pub type KittiesModule = Module<Test>;
+
+// Build genesis storage according to the mock runtime.
+// Accounts 1, 2 and 3 start with 100, minting is free and unlimited. Account 1 owns the open collection 0.
+// The pallet account starts with the existential deposit.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 100), (2, 100), (3, 100)],
+	}.assimilate_storage(&mut t).unwrap();
+	crate::GenesisConfig::<Test> {
+		marketplace_fee: Permill::zero(),
+		mint_deposit: 0,
+		max_kitties_per_account: 0,
+		mutation_rate: Permill::zero(),
+		breeding_cooldown: 0,
+		collections: vec![(1, None, 0)],
+	}.assimilate_storage(&mut t).unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,950 @@
+use crate::{Error, mock::*};
+use frame_support::{assert_ok, assert_noop, traits::{OnFinalize, OnInitialize}};
+use sp_runtime::traits::BlakeTwo256;
+use super::*;
+
+#[test]
+fn create_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		assert_eq!(KittiesModule::kitties_count(), 1);
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+		assert_eq!(KittiesModule::owned_kitties_count(1), 1);
+		assert_eq!(KittiesModule::kitty(0).unwrap().generation, 0);
+	});
+}
+
+#[test]
+fn transfer_requires_ownership() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		assert_noop!(KittiesModule::transfer(Origin::signed(2), 3, 0), Error::<Test>::NotOwner);
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(KittiesModule::owned_kitties_count(1), 0);
+		assert_eq!(KittiesModule::owned_kitties_count(2), 1);
+	});
+}
+
+#[test]
+fn asked_kitty_can_be_bought() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		assert_noop!(KittiesModule::buy(Origin::signed(2), 0, PriceLimit::Max(10), 0), Error::<Test>::KittyNotForSale);
+		assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(10)));
+		assert_noop!(KittiesModule::buy(Origin::signed(2), 0, PriceLimit::Max(5), 1), Error::<Test>::PriceTooLow);
+		assert_ok!(KittiesModule::buy(Origin::signed(2), 0, PriceLimit::Exact(10), 1));
+
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(KittiesModule::kitty_price(0), None);
+		assert_eq!(Balances::free_balance(&1), 110);
+		assert_eq!(Balances::free_balance(&2), 90);
+	});
+}
+
+#[test]
+fn bred_kitty_inherits_parent_alleles() {
+	new_test_ext().execute_with(|| {
+		Kitties::<Test>::insert(0, Kitty { dna: [0x81; 16], parents: None, generation: 0, birth: 0 });
+		Kitties::<Test>::insert(1, Kitty { dna: [0x02; 16], parents: None, generation: 0, birth: 0 });
+		KittiesCount::<Test>::put(2);
+		KittyOwners::<Test>::insert(0, 1);
+		KittyOwners::<Test>::insert(1, 1);
+		OwnedKittiesList::<Test>::append(&1, 0);
+		OwnedKittiesList::<Test>::append(&1, 1);
+
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+
+		// Without mutation every locus holds the dominant allele of the first and the recessive one of the second parent.
+		let child = KittiesModule::kitty(2).unwrap().dna;
+		for locus in 0..genetics::LOCI {
+			assert_eq!((child[locus * 2], child[locus * 2 + 1]), (0x81, 0x02));
+		}
+		assert_eq!(KittiesModule::kitty_traits(2).unwrap().fur, genetics::Gene { variant: 1, dominant: true });
+		assert_eq!(KittiesModule::kitty_traits(3), None);
+	});
+}
+
+#[test]
+fn breeding_records_lineage_and_cooldown() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::set_breeding_cooldown(Origin::root(), 10));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_noop!(KittiesModule::breed(Origin::signed(1), 0, 0), Error::<Test>::RequireDifferentParent);
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+
+		let child = KittiesModule::kitty(2).unwrap();
+		assert_eq!(child.parents, Some((0, 1)));
+		assert_eq!(child.generation, 1);
+		assert_eq!(child.birth, 1);
+		assert_eq!(KittiesModule::kitty_cooldown(0), 11);
+
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_noop!(KittiesModule::breed(Origin::signed(1), 0, 3), Error::<Test>::KittyOnCooldown);
+
+		// A generation 1 parent waits twice as long.
+		System::set_block_number(11);
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 2, 4));
+		assert_eq!(KittiesModule::kitty_cooldown(2), 31);
+		assert_eq!(KittiesModule::kitty(5).unwrap().generation, 2);
+	});
+}
+
+#[test]
+fn inbreeding_is_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 2));
+
+		// Parent and child.
+		assert_noop!(KittiesModule::breed(Origin::signed(1), 3, 0), Error::<Test>::ParentsAreRelated);
+		// Half siblings.
+		assert_noop!(KittiesModule::breed(Origin::signed(1), 3, 4), Error::<Test>::ParentsAreRelated);
+	});
+}
+
+#[test]
+fn english_auction_settles_to_highest_bidder() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(1), 0, AuctionKind::English, 10, 20, 5));
+
+		assert_noop!(KittiesModule::bid(Origin::signed(2), 0, 5), Error::<Test>::BidTooLow);
+		assert_ok!(KittiesModule::bid(Origin::signed(2), 0, 15));
+		assert_noop!(KittiesModule::bid(Origin::signed(3), 0, 15), Error::<Test>::BidTooLow);
+		assert_ok!(KittiesModule::bid(Origin::signed(3), 0, 30));
+
+		// The outbid funds are released.
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		assert_eq!(Balances::reserved_balance(&3), 30);
+		assert_noop!(KittiesModule::transfer(Origin::signed(1), 2, 0), Error::<Test>::KittyInAuction);
+
+		<KittiesModule as OnFinalize<u64>>::on_finalize(5);
+
+		assert_eq!(KittiesModule::auction(0), None);
+		assert_eq!(KittiesModule::kitty_owner(0), Some(3));
+		assert_eq!(Balances::free_balance(&1), 130);
+		assert_eq!(Balances::reserved_balance(&3), 0);
+	});
+}
+
+#[test]
+fn dutch_auction_price_falls_and_first_bid_wins() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(1), 0, AuctionKind::Dutch, 90, 10, 11));
+
+		System::set_block_number(6);
+		assert_noop!(KittiesModule::bid(Origin::signed(2), 0, 49), Error::<Test>::BidTooLow);
+		assert_ok!(KittiesModule::bid(Origin::signed(2), 0, 60));
+		assert_noop!(KittiesModule::bid(Origin::signed(3), 0, 90), Error::<Test>::AuctionEnded);
+
+		<KittiesModule as OnFinalize<u64>>::on_finalize(6);
+
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(Balances::free_balance(&2), 50);
+		assert_eq!(Balances::free_balance(&1), 150);
+
+		// The stale entry at the original end block is ignored.
+		<KittiesModule as OnFinalize<u64>>::on_finalize(11);
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+	});
+}
+
+#[test]
+fn endings_per_block_are_capped() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..4 {
+			assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		}
+		assert_ok!(KittiesModule::create_auction(Origin::signed(1), 0, AuctionKind::English, 10, 10, 5));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(1), 1, AuctionKind::English, 10, 10, 5));
+		assert_noop!(
+			KittiesModule::create_auction(Origin::signed(1), 2, AuctionKind::English, 10, 10, 5),
+			Error::<Test>::TooManyScheduled
+		);
+		assert_ok!(KittiesModule::create_auction(Origin::signed(1), 2, AuctionKind::English, 10, 10, 6));
+
+		assert_ok!(KittiesModule::make_offer(Origin::signed(2), 3, 10, 5));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(3), 3, 10, 5));
+		assert_noop!(KittiesModule::make_offer(Origin::signed(3), 2, 10, 5), Error::<Test>::TooManyScheduled);
+		// Raising an offer keeps its entry.
+		assert_ok!(KittiesModule::make_offer(Origin::signed(2), 3, 20, 5));
+		assert_eq!(KittiesModule::offers_expiring_at(5).len(), 2);
+	});
+}
+
+#[test]
+fn accepted_offer_moves_funds_and_kitty() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		assert_noop!(KittiesModule::make_offer(Origin::signed(1), 0, 10, 5), Error::<Test>::OwnKitty);
+		assert_ok!(KittiesModule::make_offer(Origin::signed(2), 0, 10, 5));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(3), 0, 20, 5));
+		assert_eq!(Balances::reserved_balance(&2), 10);
+
+		assert_noop!(KittiesModule::accept_offer(Origin::signed(2), 0, 3), Error::<Test>::NotOwner);
+		assert_ok!(KittiesModule::accept_offer(Origin::signed(1), 0, 3));
+
+		assert_eq!(KittiesModule::kitty_owner(0), Some(3));
+		assert_eq!(Balances::free_balance(&1), 120);
+		assert_eq!(Balances::free_balance(&3), 80);
+		assert_eq!(KittiesModule::kitty_offers(0), vec![2]);
+	});
+}
+
+#[test]
+fn expired_offers_are_swept() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(2), 0, 10, 5));
+
+		<KittiesModule as OnFinalize<u64>>::on_finalize(5);
+
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		assert_eq!(KittiesModule::offer((0, 2)), None);
+		assert_noop!(KittiesModule::accept_offer(Origin::signed(1), 0, 2), Error::<Test>::OfferNotFound);
+	});
+}
+
+#[test]
+fn replaced_offers_are_swept_once() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(2), 0, 10, 5));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(2), 0, 20, 5));
+		assert_eq!(KittiesModule::offers_expiring_at(5), vec![(0, 2)]);
+
+		assert_ok!(KittiesModule::make_offer(Origin::signed(2), 0, 20, 6));
+		assert!(!OffersExpiringAt::<Test>::contains_key(5));
+		assert_eq!(KittiesModule::offers_expiring_at(6), vec![(0, 2)]);
+
+		assert_ok!(KittiesModule::withdraw_offer(Origin::signed(2), 0));
+		assert!(!OffersExpiringAt::<Test>::contains_key(6));
+		assert_eq!(Balances::reserved_balance(&2), 0);
+	});
+}
+
+#[test]
+fn breeding_with_listed_sire_pays_owner() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+
+		assert_noop!(KittiesModule::breed_with_sire(Origin::signed(2), 1, 0, 10), Error::<Test>::NotListedAsSire);
+		assert_noop!(KittiesModule::list_sire(Origin::signed(2), 0, 10, 5), Error::<Test>::NotOwner);
+		assert_ok!(KittiesModule::list_sire(Origin::signed(1), 0, 10, 5));
+
+		assert_noop!(KittiesModule::breed_with_sire(Origin::signed(2), 1, 0, 9), Error::<Test>::SireFeeTooHigh);
+		assert_ok!(KittiesModule::breed_with_sire(Origin::signed(2), 1, 0, 10));
+
+		assert_eq!(Balances::free_balance(&1), 110);
+		assert_eq!(Balances::free_balance(&2), 90);
+		assert_eq!(KittiesModule::kitty_owner(2), Some(2));
+		assert_eq!(KittiesModule::kitty(2).unwrap().parents, Some((1, 0)));
+	});
+}
+
+#[test]
+fn burn_removes_kitty_and_refunds_offers() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(2), 0, 20, 5));
+
+		assert_noop!(KittiesModule::burn(Origin::signed(2), 0), Error::<Test>::NotOwner);
+		assert_ok!(KittiesModule::burn(Origin::signed(1), 0));
+
+		assert_eq!(KittiesModule::kitty(0), None);
+		assert_eq!(KittiesModule::kitty_owner(0), None);
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		assert_eq!(KittiesModule::owned_kitties_count(1), 1);
+		// Ids are not reused.
+		assert_eq!(KittiesModule::kitties_count(), 2);
+	});
+}
+
+#[test]
+fn gift_memo_is_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(10)));
+
+		assert_noop!(
+			KittiesModule::gift(Origin::signed(1), 2, 0, vec![0; MaxMemoLength::get() as usize + 1]),
+			Error::<Test>::MemoTooLong
+		);
+		assert_ok!(KittiesModule::gift(Origin::signed(1), 2, 0, b"happy birthday".to_vec()));
+
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+		assert_eq!(KittiesModule::kitty_price(0), None);
+	});
+}
+
+#[test]
+fn minting_reserves_deposit_refunded_on_burn() {
+	new_test_ext().execute_with(|| {
+		assert!(KittiesModule::set_mint_deposit(Origin::signed(1), 10).is_err());
+		assert_ok!(KittiesModule::set_mint_deposit(Origin::root(), 10));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_eq!(Balances::reserved_balance(&1), 10);
+
+		// The deposit goes back to the minter, whoever burns the kitty.
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+		assert_ok!(KittiesModule::burn(Origin::signed(2), 0));
+		assert_eq!(Balances::reserved_balance(&1), 0);
+
+		assert_ok!(KittiesModule::set_mint_deposit(Origin::root(), 200));
+		assert_noop!(KittiesModule::create(Origin::signed(1), 0), Error::<Test>::InsufficientDeposit);
+	});
+}
+
+#[test]
+fn minting_is_capped_per_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::set_max_kitties_per_account(Origin::root(), 2));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		assert_noop!(KittiesModule::create(Origin::signed(1), 0), Error::<Test>::TooManyKitties);
+		assert_noop!(KittiesModule::breed(Origin::signed(1), 0, 1), Error::<Test>::TooManyKitties);
+
+		// Transfers are not capped, and make room for minting again.
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+	});
+}
+
+#[test]
+fn owned_kitties_are_paged_in_list_order() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..5 {
+			assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		}
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 1));
+		assert_ok!(KittiesModule::ask(Origin::signed(1), 3, Some(8)));
+
+		let page = KittiesModule::owned_kitties_page(&1, None, 2);
+		assert_eq!(page.kitties.iter().map(|k| k.id).collect::<Vec<_>>(), vec![0, 2]);
+		assert_eq!(page.next, Some(3));
+		assert_eq!(page.total, 4);
+
+		let page = KittiesModule::owned_kitties_page(&1, page.next, 2);
+		assert_eq!(page.kitties.iter().map(|k| k.id).collect::<Vec<_>>(), vec![3, 4]);
+		assert_eq!(page.kitties[0].price, Some(8));
+		assert_eq!(page.next, None);
+
+		// An unknown cursor yields an empty page.
+		assert!(KittiesModule::owned_kitties_page(&1, Some(1), 10).kitties.is_empty());
+	});
+}
+
+#[test]
+fn transfer_many_is_atomic() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		}
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+
+		// Kitty 3 is not owned by the sender, nothing moves.
+		assert_noop!(KittiesModule::transfer_many(Origin::signed(1), 3, vec![0, 1, 3]), Error::<Test>::NotOwner);
+		assert_noop!(KittiesModule::transfer_many(Origin::signed(1), 3, vec![0, 1, 1]), Error::<Test>::NotOwner);
+		assert_noop!(KittiesModule::transfer_many(Origin::signed(1), 3, vec![0; 5]), Error::<Test>::BatchTooLarge);
+
+		assert_ok!(KittiesModule::transfer_many(Origin::signed(1), 3, vec![0, 2]));
+		assert_eq!(KittiesModule::owned_kitties_count(1), 1);
+		assert_eq!(KittiesModule::owned_kitties_count(3), 2);
+		assert_eq!(KittiesModule::kitty_owner(2), Some(3));
+	});
+}
+
+#[test]
+fn ask_many_is_atomic() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(1), 1, AuctionKind::English, 10, 10, 5));
+
+		assert_noop!(
+			KittiesModule::ask_many(Origin::signed(1), vec![(0, Some(10)), (1, Some(20))]),
+			Error::<Test>::KittyInAuction
+		);
+
+		assert_ok!(KittiesModule::ask_many(Origin::signed(1), vec![(0, Some(10))]));
+		assert_eq!(KittiesModule::kitty_price(0), Some(10));
+		assert_ok!(KittiesModule::ask_many(Origin::signed(1), vec![(0, None)]));
+		assert_eq!(KittiesModule::kitty_price(0), None);
+	});
+}
+
+#[test]
+fn breed_many_is_atomic() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..4 {
+			assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		}
+		assert_ok!(KittiesModule::set_breeding_cooldown(Origin::root(), 10));
+
+		// The second pair reuses a parent that is on cooldown after the first one.
+		assert_noop!(
+			KittiesModule::breed_many(Origin::signed(1), vec![(0, 1), (1, 2)]),
+			Error::<Test>::KittyOnCooldown
+		);
+
+		assert_ok!(KittiesModule::breed_many(Origin::signed(1), vec![(0, 1), (2, 3)]));
+		assert_eq!(KittiesModule::kitties_count(), 6);
+		assert_eq!(KittiesModule::kitty(5).unwrap().parents, Some((2, 3)));
+	});
+}
+
+#[test]
+fn approved_spender_can_transfer_once() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		assert_noop!(KittiesModule::transfer_from(Origin::signed(2), 1, 3, 0), Error::<Test>::NotApproved);
+		assert_noop!(KittiesModule::approve(Origin::signed(2), Some(2), 0), Error::<Test>::NotApproved);
+		assert_ok!(KittiesModule::approve(Origin::signed(1), Some(2), 0));
+		assert_noop!(KittiesModule::transfer_from(Origin::signed(2), 3, 2, 0), Error::<Test>::NotOwner);
+
+		assert_ok!(KittiesModule::transfer_from(Origin::signed(2), 1, 3, 0));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(3));
+
+		// The approval is cleared on transfer.
+		assert_eq!(KittiesModule::kitty_approval(0), None);
+		assert_noop!(KittiesModule::transfer_from(Origin::signed(2), 3, 2, 0), Error::<Test>::NotApproved);
+	});
+}
+
+#[test]
+fn operator_can_transfer_and_approve_all_kitties() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::set_approval_for_all(Origin::signed(1), 2, true));
+
+		assert_ok!(KittiesModule::transfer_from(Origin::signed(2), 1, 2, 0));
+		assert_ok!(KittiesModule::approve(Origin::signed(2), Some(3), 1));
+		assert_ok!(KittiesModule::transfer_from(Origin::signed(3), 1, 3, 1));
+		assert_eq!(KittiesModule::kitty_owner(1), Some(3));
+
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::set_approval_for_all(Origin::signed(1), 2, false));
+		assert_noop!(KittiesModule::transfer_from(Origin::signed(2), 1, 2, 2), Error::<Test>::NotApproved);
+	});
+}
+
+#[test]
+fn breeder_earns_royalty_on_resale() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_eq!(KittiesModule::kitty_breeder(0), Some(1));
+
+		assert_noop!(KittiesModule::set_royalty(Origin::signed(2), 0, Permill::from_percent(5)), Error::<Test>::NotBreeder);
+		assert_noop!(KittiesModule::set_royalty(Origin::signed(1), 0, Permill::from_percent(20)), Error::<Test>::RoyaltyTooHigh);
+		assert_ok!(KittiesModule::set_royalty(Origin::signed(1), 0, Permill::from_percent(10)));
+
+		// No royalty when the breeder sells.
+		assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(20)));
+		assert_ok!(KittiesModule::buy(Origin::signed(2), 0, PriceLimit::Exact(20), 1));
+		assert_eq!(Balances::free_balance(&1), 120);
+
+		assert_ok!(KittiesModule::ask(Origin::signed(2), 0, Some(50)));
+		assert_ok!(KittiesModule::buy(Origin::signed(3), 0, PriceLimit::Exact(50), 3));
+		assert_eq!(Balances::free_balance(&1), 125);
+		assert_eq!(Balances::free_balance(&2), 80 + 45);
+		assert_eq!(Balances::free_balance(&3), 50);
+	});
+}
+
+#[test]
+fn auction_settlement_pays_royalty() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::set_royalty(Origin::signed(1), 0, Permill::from_percent(10)));
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+
+		assert_ok!(KittiesModule::create_auction(Origin::signed(2), 0, AuctionKind::English, 10, 10, 5));
+		assert_ok!(KittiesModule::bid(Origin::signed(3), 0, 40));
+		<KittiesModule as OnFinalize<u64>>::on_finalize(5);
+
+		assert_eq!(KittiesModule::kitty_owner(0), Some(3));
+		assert_eq!(Balances::free_balance(&1), 104);
+		assert_eq!(Balances::free_balance(&2), 136);
+		assert_eq!(Balances::reserved_balance(&3), 0);
+	});
+}
+
+#[test]
+fn unpayable_auction_is_not_settled() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::set_royalty(Origin::signed(1), 0, Permill::from_percent(10)));
+		// Account 4 has no balance, funds can not be repatriated to it.
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 4, 0));
+
+		assert_ok!(KittiesModule::create_auction(Origin::signed(4), 0, AuctionKind::English, 10, 10, 5));
+		assert_ok!(KittiesModule::bid(Origin::signed(3), 0, 40));
+		<KittiesModule as OnFinalize<u64>>::on_finalize(5);
+
+		// The royalty is rolled back with the failed payment to the seller.
+		assert_eq!(KittiesModule::kitty_owner(0), Some(4));
+		assert_eq!(Balances::free_balance(&1), 100);
+		assert_eq!(Balances::free_balance(&3), 100);
+		assert_eq!(Balances::reserved_balance(&3), 0);
+		assert_eq!(KittiesModule::auction(0), None);
+	});
+}
+
+#[test]
+fn buy_with_max_price_pays_asked_price() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(10)));
+
+		assert_noop!(KittiesModule::buy(Origin::signed(1), 0, PriceLimit::Max(10), 1), Error::<Test>::OwnKitty);
+		assert_noop!(KittiesModule::buy(Origin::signed(2), 0, PriceLimit::Exact(15), 1), Error::<Test>::PriceMismatch);
+		assert_ok!(KittiesModule::buy(Origin::signed(2), 0, PriceLimit::Max(15), 1));
+
+		assert_eq!(Balances::free_balance(&1), 110);
+		assert_eq!(Balances::free_balance(&2), 90);
+	});
+}
+
+#[test]
+fn raised_price_in_same_block_fails_pending_buy() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(10)));
+		let nonce = KittiesModule::price_nonce(0);
+
+		// The seller front-runs the buy with a higher price, still below the buyer's maximum.
+		assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(20)));
+		assert_noop!(KittiesModule::buy(Origin::signed(2), 0, PriceLimit::Max(30), nonce), Error::<Test>::PriceChanged);
+
+		assert_ok!(KittiesModule::buy(Origin::signed(2), 0, PriceLimit::Max(30), nonce + 1));
+		assert_eq!(Balances::free_balance(&2), 80);
+	});
+}
+
+#[test]
+fn any_ask_invalidates_pending_buys() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(10)));
+
+		// Lowered, then asked again at the very same price.
+		assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(5)));
+		assert_noop!(KittiesModule::buy(Origin::signed(2), 0, PriceLimit::Exact(5), 1), Error::<Test>::PriceChanged);
+		assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(5)));
+		assert_noop!(KittiesModule::buy(Origin::signed(2), 0, PriceLimit::Exact(5), 2), Error::<Test>::PriceChanged);
+
+		// Withdrawn from sale, and put back through a batch.
+		assert_ok!(KittiesModule::ask(Origin::signed(1), 0, None));
+		assert_noop!(KittiesModule::buy(Origin::signed(2), 0, PriceLimit::Max(10), 4), Error::<Test>::KittyNotForSale);
+		assert_ok!(KittiesModule::ask_many(Origin::signed(1), vec![(0, Some(10))]));
+		assert_noop!(KittiesModule::buy(Origin::signed(2), 0, PriceLimit::Max(10), 4), Error::<Test>::PriceChanged);
+		assert_ok!(KittiesModule::buy(Origin::signed(2), 0, PriceLimit::Max(10), 5));
+	});
+}
+
+#[test]
+fn second_buyer_in_same_block_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(10)));
+
+		assert_ok!(KittiesModule::buy(Origin::signed(2), 0, PriceLimit::Exact(10), 1));
+		assert_noop!(KittiesModule::buy(Origin::signed(3), 0, PriceLimit::Exact(10), 1), Error::<Test>::KittyNotForSale);
+
+		// The new owner asks the same price, the stale nonce still fails.
+		assert_ok!(KittiesModule::ask(Origin::signed(2), 0, Some(10)));
+		assert_noop!(KittiesModule::buy(Origin::signed(3), 0, PriceLimit::Exact(10), 1), Error::<Test>::PriceChanged);
+		assert_eq!(KittiesModule::owned_kitties_page(&2, None, 1).kitties[0].price_nonce, 3);
+	});
+}
+
+#[test]
+fn transfer_withdraws_kitty_from_sale() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(10)));
+		let nonce = KittiesModule::price_nonce(0);
+
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+		assert_eq!(KittiesModule::kitty_price(0), None);
+		assert_noop!(KittiesModule::buy(Origin::signed(3), 0, PriceLimit::Exact(10), nonce), Error::<Test>::KittyNotForSale);
+
+		// Not even once the new owner asks the same price.
+		assert_ok!(KittiesModule::ask(Origin::signed(2), 0, Some(10)));
+		assert_noop!(KittiesModule::buy(Origin::signed(3), 0, PriceLimit::Exact(10), nonce), Error::<Test>::PriceChanged);
+		assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+	});
+}
+
+#[test]
+fn marketplace_fee_goes_to_pallet_account() {
+	new_test_ext().execute_with(|| {
+		assert!(KittiesModule::set_marketplace_fee(Origin::signed(1), Permill::from_percent(5)).is_err());
+		assert_ok!(KittiesModule::set_marketplace_fee(Origin::root(), Permill::from_percent(5)));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::set_royalty(Origin::signed(1), 0, Permill::from_percent(10)));
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+		assert_ok!(KittiesModule::ask(Origin::signed(2), 0, Some(40)));
+
+		assert_ok!(KittiesModule::buy(Origin::signed(3), 0, PriceLimit::Exact(40), 2));
+
+		// 5% fee and 10% royalty are both taken from the full price.
+		assert_eq!(Balances::free_balance(&KittiesModule::account_id()), 1 + 2);
+		assert_eq!(Balances::free_balance(&1), 104);
+		assert_eq!(Balances::free_balance(&2), 134);
+		assert_eq!(Balances::free_balance(&3), 60);
+	});
+}
+
+#[test]
+fn fee_below_existential_deposit_is_collected() {
+	ExistentialDeposit::set(10);
+	new_test_ext().execute_with(|| {
+		assert_eq!(Balances::free_balance(&KittiesModule::account_id()), 10);
+		assert_ok!(KittiesModule::set_marketplace_fee(Origin::root(), Permill::from_percent(5)));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(40)));
+
+		assert_ok!(KittiesModule::buy(Origin::signed(2), 0, PriceLimit::Exact(40), 1));
+		assert_eq!(Balances::free_balance(&KittiesModule::account_id()), 12);
+		assert_eq!(Balances::free_balance(&1), 138);
+	});
+}
+
+#[test]
+fn failed_payment_reverts_buy() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::set_marketplace_fee(Origin::root(), Permill::from_percent(10)));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(100)));
+
+		// The fee is paid, then the buyer can not pay the seller and keep its account alive.
+		assert!(KittiesModule::buy(Origin::signed(2), 0, PriceLimit::Exact(100), 1).is_err());
+		assert_eq!(Balances::free_balance(&KittiesModule::account_id()), 1);
+		assert_eq!(Balances::free_balance(&2), 100);
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+	});
+}
+
+#[test]
+fn borrowed_kitty_can_breed_but_not_move() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(2), 0));
+
+		assert_noop!(KittiesModule::borrow(Origin::signed(2), 0, 10), Error::<Test>::NotOfferedForLoan);
+		assert_noop!(KittiesModule::offer_loan(Origin::signed(1), 0, 10, 0), Error::<Test>::InvalidLoanDuration);
+		assert_ok!(KittiesModule::offer_loan(Origin::signed(1), 0, 10, 5));
+		assert_noop!(KittiesModule::borrow(Origin::signed(2), 0, 5), Error::<Test>::LoanFeeMismatch);
+		assert_ok!(KittiesModule::borrow(Origin::signed(2), 0, 10));
+
+		assert_eq!(Balances::free_balance(&1), 110);
+		assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+		assert_eq!(KittiesModule::custodian(0), Some(2));
+		assert_eq!(KittiesModule::owned_kitties_count(1), 1);
+		assert_eq!(KittiesModule::borrowed_kitties_count(2), 1);
+
+		// Neither the owner nor the borrower can move the kitty.
+		assert_noop!(KittiesModule::transfer(Origin::signed(1), 3, 0), Error::<Test>::KittyOnLoan);
+		assert_noop!(KittiesModule::ask(Origin::signed(1), 0, Some(10)), Error::<Test>::KittyOnLoan);
+		assert_noop!(KittiesModule::burn(Origin::signed(1), 0), Error::<Test>::KittyOnLoan);
+		assert_noop!(KittiesModule::transfer(Origin::signed(2), 3, 0), Error::<Test>::NotOwner);
+
+		// Only the borrower can breed with it.
+		assert_noop!(KittiesModule::breed(Origin::signed(1), 0, 1), Error::<Test>::NotOwner);
+		assert_ok!(KittiesModule::breed(Origin::signed(2), 0, 1));
+		assert_eq!(KittiesModule::kitty_owner(2), Some(2));
+	});
+}
+
+#[test]
+fn loan_reverts_at_expiry() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::offer_loan(Origin::signed(1), 0, 0, 5));
+		assert_ok!(KittiesModule::borrow(Origin::signed(2), 0, 0));
+		assert_eq!(KittiesModule::loan(0).unwrap().end, 6);
+
+		<KittiesModule as OnInitialize<u64>>::on_initialize(6);
+
+		assert_eq!(KittiesModule::loan(0), None);
+		assert_eq!(KittiesModule::custodian(0), Some(1));
+		assert_eq!(KittiesModule::borrowed_kitties_count(2), 0);
+		assert_eq!(KittiesModule::borrowed_kitties((2, Some(0))), None);
+		assert_eq!(KittiesModule::owned_kitties_count(1), 1);
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 3, 0));
+	});
+}
+
+#[test]
+fn metadata_reserves_deposit_per_byte() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		assert_noop!(
+			KittiesModule::set_metadata(Origin::signed(2), 0, b"Tom".to_vec(), vec![], None),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittiesModule::set_metadata(Origin::signed(1), 0, vec![b'a'; 17], vec![], None),
+			Error::<Test>::NameTooLong
+		);
+		assert_ok!(KittiesModule::set_metadata(Origin::signed(1), 0, b"Tom".to_vec(), b"grey".to_vec(), Some([7; 32])));
+
+		assert_eq!(KittiesModule::metadata(0).unwrap().name, b"Tom".to_vec());
+		assert_eq!(Balances::reserved_balance(&1), 3 + 4 + 32);
+
+		// The new owner pays for its own metadata, the previous deposit goes back.
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+		assert_ok!(KittiesModule::set_metadata(Origin::signed(2), 0, b"Jerry".to_vec(), vec![], None));
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(Balances::reserved_balance(&2), 5);
+
+		assert_ok!(KittiesModule::clear_metadata(Origin::signed(2), 0));
+		assert_eq!(KittiesModule::metadata(0), None);
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		assert_noop!(KittiesModule::clear_metadata(Origin::signed(2), 0), Error::<Test>::NoMetadata);
+	});
+}
+
+#[test]
+fn burn_clears_metadata() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::set_metadata(Origin::signed(1), 0, b"Tom".to_vec(), vec![], None));
+		assert_noop!(
+			KittiesModule::set_metadata(Origin::signed(1), 0, vec![], vec![0; 120], None),
+			Error::<Test>::DescriptionTooLong
+		);
+
+		assert_ok!(KittiesModule::burn(Origin::signed(1), 0));
+
+		assert_eq!(KittiesModule::metadata(0), None);
+		assert_eq!(KittiesModule::metadata_deposit(0), None);
+		assert_eq!(Balances::reserved_balance(&1), 0);
+	});
+}
+
+#[test]
+fn commit_reveal_breeds_in_later_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		let secret = [42u8; 32];
+		let commitment = BlakeTwo256::hash_of(&(1u64, secret));
+		assert_ok!(KittiesModule::commit_breed(Origin::signed(1), 0, 1, commitment));
+		assert_eq!(Balances::reserved_balance(&1), 10);
+		assert_noop!(
+			KittiesModule::commit_breed(Origin::signed(1), 0, 1, commitment),
+			Error::<Test>::CommitExists
+		);
+
+		assert_noop!(KittiesModule::reveal_breed(Origin::signed(1), secret), Error::<Test>::RevealTooEarly);
+		// The commitment is bound to the committing account.
+		assert_noop!(KittiesModule::reveal_breed(Origin::signed(2), secret), Error::<Test>::CommitNotFound);
+
+		System::set_block_number(2);
+		<KittiesModule as OnInitialize<u64>>::on_initialize(2);
+		assert!(KittiesModule::commit_seed(11).is_some());
+		assert_ok!(KittiesModule::reveal_breed(Origin::signed(1), secret));
+
+		assert_eq!(KittiesModule::kitties_count(), 3);
+		assert_eq!(KittiesModule::kitty_owner(2), Some(1));
+		assert_eq!(KittiesModule::breed_commit((1, commitment)), None);
+		assert_eq!(Balances::reserved_balance(&1), 0);
+	});
+}
+
+#[test]
+fn committed_parents_stay_with_the_breeder() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(20)));
+
+		let secret = [5u8; 32];
+		let commitment = BlakeTwo256::hash_of(&(1u64, secret));
+		assert_ok!(KittiesModule::commit_breed(Origin::signed(1), 0, 1, commitment));
+		assert_eq!(KittiesModule::kitty_price(0), None);
+
+		// Nothing the reveal depends on can change before it.
+		assert_noop!(KittiesModule::transfer(Origin::signed(1), 2, 0), Error::<Test>::KittyCommitted);
+		assert_noop!(KittiesModule::burn(Origin::signed(1), 1), Error::<Test>::KittyCommitted);
+		assert_noop!(KittiesModule::list_sire(Origin::signed(1), 1, 5, 20), Error::<Test>::KittyCommitted);
+		assert_noop!(KittiesModule::breed(Origin::signed(1), 0, 2), Error::<Test>::KittyCommitted);
+		assert_noop!(
+			KittiesModule::commit_breed(Origin::signed(1), 1, 2, BlakeTwo256::hash_of(&(1u64, [6u8; 32]))),
+			Error::<Test>::KittyCommitted
+		);
+
+		System::set_block_number(2);
+		<KittiesModule as OnInitialize<u64>>::on_initialize(2);
+		assert_ok!(KittiesModule::reveal_breed(Origin::signed(1), secret));
+		assert_eq!(KittiesModule::committed_kitty(0), None);
+		assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+
+		// Forfeiting a commitment releases its parents as well.
+		let commitment = BlakeTwo256::hash_of(&(1u64, [6u8; 32]));
+		assert_ok!(KittiesModule::commit_breed(Origin::signed(1), 2, 3, commitment));
+		<KittiesModule as OnFinalize<u64>>::on_finalize(12);
+		assert_eq!(KittiesModule::committed_kitty(2), None);
+		assert_ok!(KittiesModule::burn(Origin::signed(1), 2));
+	});
+}
+
+#[test]
+fn reveal_seed_is_fixed_after_commit_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		let secret = [9u8; 32];
+		assert_ok!(KittiesModule::commit_breed(Origin::signed(1), 0, 1, BlakeTwo256::hash_of(&(1u64, secret))));
+
+		System::set_block_number(2);
+		<KittiesModule as OnInitialize<u64>>::on_initialize(2);
+		let seed = KittiesModule::commit_seed(11);
+
+		// Later blocks do not draw the seed again, waiting for a better one does not change the DNA.
+		System::set_block_number(5);
+		<KittiesModule as OnInitialize<u64>>::on_initialize(5);
+		assert_eq!(KittiesModule::commit_seed(11), seed);
+		assert_ok!(KittiesModule::reveal_breed(Origin::signed(1), secret));
+
+		<KittiesModule as OnFinalize<u64>>::on_finalize(11);
+		assert_eq!(KittiesModule::commit_seed(11), None);
+	});
+}
+
+#[test]
+fn unrevealed_commit_forfeits_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+
+		let secret = [7u8; 32];
+		let commitment = BlakeTwo256::hash_of(&(1u64, secret));
+		assert_ok!(KittiesModule::commit_breed(Origin::signed(1), 0, 1, commitment));
+
+		System::set_block_number(11);
+		<KittiesModule as OnFinalize<u64>>::on_finalize(11);
+
+		assert_eq!(KittiesModule::breed_commit((1, commitment)), None);
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(Balances::free_balance(&1), 90);
+
+		System::set_block_number(12);
+		assert_noop!(KittiesModule::reveal_breed(Origin::signed(1), secret), Error::<Test>::CommitNotFound);
+	});
+}
+
+#[test]
+fn collection_enforces_allowlist_price_and_cap() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_collection(Origin::signed(2), Some(2), 10, true, false));
+		assert_eq!(Balances::reserved_balance(&2), 5);
+		assert_noop!(KittiesModule::create(Origin::signed(3), 1), Error::<Test>::NotAllowlisted);
+		assert_noop!(KittiesModule::create(Origin::signed(3), 2), Error::<Test>::CollectionNotFound);
+		assert_noop!(
+			KittiesModule::set_allowlist(Origin::signed(3), 1, vec![3], true),
+			Error::<Test>::NotCollectionOwner
+		);
+
+		assert_ok!(KittiesModule::set_allowlist(Origin::signed(2), 1, vec![3], true));
+		assert_ok!(KittiesModule::create(Origin::signed(3), 1));
+		assert_eq!(KittiesModule::kitty_collection(0), 1);
+		assert_eq!(Balances::free_balance(&3), 90);
+		assert_eq!(Balances::free_balance(&2), 105);
+
+		// The owner mints for free, the cap counts every kitty of the collection.
+		assert_ok!(KittiesModule::create(Origin::signed(2), 1));
+		assert_eq!(Balances::free_balance(&2), 105);
+		assert_noop!(KittiesModule::create(Origin::signed(3), 1), Error::<Test>::CollectionFull);
+		assert_ok!(KittiesModule::transfer(Origin::signed(2), 3, 1));
+		assert_noop!(KittiesModule::breed(Origin::signed(3), 0, 1), Error::<Test>::CollectionFull);
+
+		assert_ok!(KittiesModule::create(Origin::signed(3), 0));
+	});
+}
+
+#[test]
+fn breeding_follows_collection_allowlist_and_price() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_collection(Origin::signed(2), None, 10, true, false));
+		assert_ok!(KittiesModule::create(Origin::signed(2), 1));
+		assert_ok!(KittiesModule::create(Origin::signed(2), 1));
+		assert_ok!(KittiesModule::transfer(Origin::signed(2), 3, 0));
+		assert_ok!(KittiesModule::transfer(Origin::signed(2), 3, 1));
+
+		assert_noop!(KittiesModule::breed(Origin::signed(3), 0, 1), Error::<Test>::NotAllowlisted);
+
+		assert_ok!(KittiesModule::set_allowlist(Origin::signed(2), 1, vec![3], true));
+		assert_ok!(KittiesModule::breed(Origin::signed(3), 0, 1));
+		assert_eq!(KittiesModule::kitty_collection(2), 1);
+		assert_eq!(Balances::free_balance(&3), 90);
+		assert_eq!(Balances::free_balance(&2), 105);
+	});
+}
+
+#[test]
+fn destroying_empty_collection_returns_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(KittiesModule::destroy_collection(Origin::signed(1), 0), Error::<Test>::PermanentCollection);
+
+		assert_ok!(KittiesModule::create_collection(Origin::signed(2), None, 0, true, false));
+		assert_ok!(KittiesModule::create(Origin::signed(2), 1));
+		assert_ok!(KittiesModule::set_allowlist(Origin::signed(2), 1, vec![3, 3], true));
+		assert_eq!(KittiesModule::collection_allowlist_size(1), 1);
+
+		assert_noop!(KittiesModule::destroy_collection(Origin::signed(3), 1), Error::<Test>::NotCollectionOwner);
+		assert_noop!(KittiesModule::destroy_collection(Origin::signed(2), 1), Error::<Test>::CollectionNotEmpty);
+		assert_ok!(KittiesModule::burn(Origin::signed(2), 0));
+		assert_noop!(KittiesModule::destroy_collection(Origin::signed(2), 1), Error::<Test>::AllowlistNotEmpty);
+		assert_ok!(KittiesModule::set_allowlist(Origin::signed(2), 1, vec![3], false));
+
+		assert_ok!(KittiesModule::destroy_collection(Origin::signed(2), 1));
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		assert_eq!(Balances::free_balance(&2), 100);
+		assert!(KittiesModule::collection(1).is_none());
+		assert_noop!(KittiesModule::create(Origin::signed(2), 1), Error::<Test>::CollectionNotFound);
+	});
+}
+
+#[test]
+fn cross_collection_breeding_is_opt_in() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create(Origin::signed(1), 0));
+		assert_ok!(KittiesModule::create_collection(Origin::signed(2), None, 0, false, true));
+		assert_ok!(KittiesModule::create(Origin::signed(1), 1));
+
+		assert_noop!(KittiesModule::breed(Origin::signed(1), 0, 1), Error::<Test>::CrossBreedingNotAllowed);
+		assert_noop!(
+			KittiesModule::update_collection(Origin::signed(2), 0, 0, false, true),
+			Error::<Test>::NotCollectionOwner
+		);
+
+		assert_ok!(KittiesModule::update_collection(Origin::signed(1), 0, 0, false, true));
+		assert_ok!(KittiesModule::breed(Origin::signed(1), 1, 0));
+		// The kitty joins the collection of its first parent.
+		assert_eq!(KittiesModule::kitty_collection(2), 1);
+		assert_eq!(KittiesModule::collection(1).unwrap().supply, 2);
+	});
+}
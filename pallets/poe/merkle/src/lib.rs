@@ -0,0 +1,97 @@
+//! Off-chain Merkle trees for `create_batch_claim` of the proof of existence pallet.
+//!
+//! Build a `MerkleTree` of the digests of a batch of documents, claim its `root` with its
+//! `leaf_count`, and hand out the `proof` of each document to check it against the claim with the
+//! `poe_verifyBatchLeaf` RPC. Hashing is the one of `pallet_poe::merkle`.
+
+pub use pallet_poe::merkle::{leaf_hash, node_hash, root_from_proof};
+
+/// A Merkle tree of document digests, every level of it from the leaves up.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleTree {
+	levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+	/// Tree of `leaves` in order, `None` without leaves or with more than `u32::max_value()` of them.
+	pub fn new(leaves: &[[u8; 32]]) -> Option<Self> {
+		if leaves.is_empty() || leaves.len() > u32::max_value() as usize {
+			return None;
+		}
+
+		let mut levels = vec![leaves.iter().map(leaf_hash).collect::<Vec<_>>()];
+		while levels[levels.len() - 1].len() > 1 {
+			let level = levels[levels.len() - 1].chunks(2)
+				.map(|pair| match pair {
+					[left, right] => node_hash(left, right),
+					[last] => *last,
+					_ => unreachable!("chunks of two"),
+				})
+				.collect();
+			levels.push(level);
+		}
+		Some(MerkleTree { levels })
+	}
+
+	/// The root to claim.
+	pub fn root(&self) -> [u8; 32] {
+		self.levels[self.levels.len() - 1][0]
+	}
+
+	/// The number of leaves to claim the root with.
+	pub fn leaf_count(&self) -> u32 {
+		self.levels[0].len() as u32
+	}
+
+	/// Sibling hashes of the path from the leaf at `index` to the root, `None` if there is no such leaf.
+	pub fn proof(&self, index: u32) -> Option<Vec<[u8; 32]>> {
+		let mut index = index as usize;
+		if index >= self.levels[0].len() {
+			return None;
+		}
+
+		let mut proof = Vec::new();
+		for level in &self.levels[..self.levels.len() - 1] {
+			let sibling = if index % 2 == 1 { Some(index - 1) } else if index + 1 < level.len() { Some(index + 1) } else { None };
+			if let Some(sibling) = sibling {
+				proof.push(level[sibling]);
+			}
+			index /= 2;
+		}
+		Some(proof)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn leaves(n: u8) -> Vec<[u8; 32]> {
+		(0..n).map(|i| [i; 32]).collect()
+	}
+
+	#[test]
+	fn proofs_lead_to_the_root() {
+		for n in 1..=9 {
+			let leaves = leaves(n);
+			let tree = MerkleTree::new(&leaves).unwrap();
+			assert_eq!(tree.leaf_count(), n as u32);
+			for (index, leaf) in leaves.iter().enumerate() {
+				let proof = tree.proof(index as u32).unwrap();
+				assert_eq!(root_from_proof(leaf, index as u32, tree.leaf_count(), &proof), Some(tree.root()));
+			}
+			assert_eq!(tree.proof(n as u32), None);
+		}
+	}
+
+	#[test]
+	fn proofs_do_not_fit_other_leaves() {
+		let leaves = leaves(5);
+		let tree = MerkleTree::new(&leaves).unwrap();
+		let proof = tree.proof(1).unwrap();
+
+		assert_ne!(root_from_proof(&leaves[2], 1, 5, &proof), Some(tree.root()));
+		assert_eq!(root_from_proof(&leaves[1], 1, 2, &proof), None);
+		assert_eq!(MerkleTree::new(&[]), None);
+	}
+}
@@ -0,0 +1,13 @@
+//! Runtime API definition of the proof of existence pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	pub trait PoeApi {
+		/// Whether `leaf` is at `index` of the live batch claim of Merkle root `root`, as proven by
+		/// the sibling hashes in `proof`.
+		fn verify_batch_leaf(root: [u8; 32], leaf: [u8; 32], index: u32, proof: Vec<[u8; 32]>) -> bool;
+	}
+}
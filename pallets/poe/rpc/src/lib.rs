@@ -0,0 +1,70 @@
+//! RPC interface of the proof of existence pallet.
+
+use std::sync::Arc;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::H256;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_poe_rpc_runtime_api::PoeApi as PoeRuntimeApi;
+
+/// Proof of existence RPC methods.
+#[rpc]
+pub trait PoeApi<BlockHash> {
+	/// Whether `leaf` is at `index` of the batch claim of Merkle root `root`. `proof` holds the
+	/// sibling hashes of the path from the leaf to the root, from the leaves up.
+	#[rpc(name = "poe_verifyBatchLeaf")]
+	fn verify_batch_leaf(
+		&self,
+		root: H256,
+		leaf: H256,
+		index: u32,
+		proof: Vec<H256>,
+		at: Option<BlockHash>,
+	) -> Result<bool>;
+}
+
+/// Proof of existence RPC methods backed by the runtime API.
+pub struct Poe<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Poe<C, B> {
+	/// Proof of existence RPC over the given client.
+	pub fn new(client: Arc<C>) -> Self {
+		Poe { client, _marker: Default::default() }
+	}
+}
+
+fn runtime_error<E: std::fmt::Debug>(err: E) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(1),
+		message: "Unable to query claims".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+impl<C, Block> PoeApi<<Block as BlockT>::Hash> for Poe<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: PoeRuntimeApi<Block>,
+{
+	fn verify_batch_leaf(
+		&self,
+		root: H256,
+		leaf: H256,
+		index: u32,
+		proof: Vec<H256>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<bool> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let proof = proof.into_iter().map(|hash| hash.to_fixed_bytes()).collect();
+		self.client.runtime_api()
+			.verify_batch_leaf(&at, root.to_fixed_bytes(), leaf.to_fixed_bytes(), index, proof)
+			.map_err(runtime_error)
+	}
+}
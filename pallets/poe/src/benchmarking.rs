@@ -0,0 +1,171 @@
+//! Benchmarks of the proof of existence pallet.
+
+use super::*;
+use frame_benchmarking::{benchmarks, account, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::prelude::*;
+
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller = whitelisted_caller();
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+	caller
+}
+
+/// Worst case notarisation: a fee paid to an account that does not exist yet.
+fn set_up_fee<T: Config>() {
+	let receiver: T::AccountId = account("receiver", 0, 0);
+	NotarisationFee::<T>::put((receiver, T::Currency::minimum_balance().saturating_mul(10u32.into())));
+}
+
+/// The longest metadata a claim can carry.
+fn full_metadata<T: Config>() -> ClaimMetadata {
+	ClaimMetadata {
+		description: vec![0; T::MaxDescriptionLength::get() as usize],
+		mime_type: vec![0; T::MaxMimeTypeLength::get() as usize],
+		uri_hash: Some([0; 32]),
+	}
+}
+
+/// Creates `claim` owned by `owner` and the caller with a threshold of two, `owner` pays the
+/// deposit and approves `action`, so the approval of the caller carries it out.
+fn shared_claim<T: Config>(owner: &T::AccountId, caller: &T::AccountId, claim: &[u8], action: Option<ClaimAction<T::AccountId>>) {
+	T::Currency::make_free_balance_be(owner, BalanceOf::<T>::max_value() / 2u32.into());
+	Module::<T>::create_shared_claim(
+		RawOrigin::Signed(owner.clone()).into(),
+		claim.to_vec(),
+		vec![owner.clone(), caller.clone()],
+		2,
+	).expect("owner can pay the deposit");
+	let approved = match action {
+		Some(ClaimAction::Revoke) => Module::<T>::revoke_claim(RawOrigin::Signed(owner.clone()).into(), claim.to_vec()),
+		Some(ClaimAction::Transfer(dest)) => Module::<T>::transfer_claim(RawOrigin::Signed(owner.clone()).into(), claim.to_vec(), dest),
+		None => Ok(()),
+	};
+	approved.expect("owner approves");
+}
+
+benchmarks! {
+	create_claim {
+		let l in 1 .. T::MaxProofLength::get() as u32;
+		set_up_fee::<T>();
+		let caller = funded_caller::<T>();
+		let claim = vec![0u8; l as usize];
+	}: _(RawOrigin::Signed(caller.clone()), claim.clone())
+	verify {
+		assert_eq!(Module::<T>::proofs(&claim).map(|proof| proof.owners), Some(vec![caller]));
+	}
+
+	create_digest_claim {
+		set_up_fee::<T>();
+		let caller = funded_caller::<T>();
+		let claim = (HashAlgo::Sha256, [1u8; 32]).encode();
+	}: _(RawOrigin::Signed(caller.clone()), HashAlgo::Sha256, [1u8; 32])
+	verify {
+		assert_eq!(Module::<T>::proofs(&claim).map(|proof| proof.owners), Some(vec![caller]));
+	}
+
+	create_batch_claim {
+		set_up_fee::<T>();
+		let caller = funded_caller::<T>();
+		let root = [1u8; 32];
+	}: _(RawOrigin::Signed(caller.clone()), root, u32::max_value())
+	verify {
+		assert_eq!(Module::<T>::batch_leaf_count(Module::<T>::batch_claim(root)), Some(u32::max_value()));
+	}
+
+	create_shared_claim {
+		let l in 1 .. T::MaxProofLength::get() as u32;
+		let o in 1 .. T::MaxOwners::get();
+		set_up_fee::<T>();
+		let caller = funded_caller::<T>();
+		let claim = vec![0u8; l as usize];
+		// The owners are sorted and deduplicated, the caller is one of them.
+		let mut owners: Vec<T::AccountId> = (1..o).map(|i| account("owner", i, 0)).collect();
+		owners.push(caller.clone());
+	}: _(RawOrigin::Signed(caller.clone()), claim.clone(), owners, o)
+	verify {
+		assert_eq!(Module::<T>::proofs(&claim).map(|proof| proof.owners.len() as u32), Some(o));
+	}
+
+	create_claim_with_details {
+		let l in 1 .. T::MaxProofLength::get() as u32;
+		set_up_fee::<T>();
+		let caller = funded_caller::<T>();
+		let claim = vec![0u8; l as usize];
+	}: _(RawOrigin::Signed(caller), claim.clone(), Some(full_metadata::<T>()), Some(T::BlockNumber::max_value()))
+	verify {
+		assert_eq!(Module::<T>::metadata(&claim), Some(full_metadata::<T>()));
+	}
+
+	set_claim_metadata {
+		let l in 1 .. T::MaxProofLength::get() as u32;
+		// The deposit reserved by the other owner is refunded.
+		let owner: T::AccountId = account("owner", 0, 0);
+		let caller = funded_caller::<T>();
+		let claim = vec![0u8; l as usize];
+		shared_claim::<T>(&owner, &caller, &claim, None);
+	}: _(RawOrigin::Signed(caller.clone()), claim.clone(), Some(full_metadata::<T>()))
+	verify {
+		assert_eq!(Module::<T>::deposit(&claim).map(|(depositor, _)| depositor), Some(caller));
+	}
+
+	renew_claim {
+		let l in 1 .. T::MaxProofLength::get() as u32;
+		let caller = funded_caller::<T>();
+		let claim = vec![0u8; l as usize];
+		// The claim moves from the lapse list of its old expiry to that of the new one.
+		let expiry = frame_system::Module::<T>::block_number() + 10u32.into();
+		Module::<T>::create_claim_with_details(RawOrigin::Signed(caller.clone()).into(), claim.clone(), None, Some(expiry))?;
+	}: _(RawOrigin::Signed(caller), claim.clone(), Some(expiry + 10u32.into()))
+	verify {
+		assert_eq!(Module::<T>::expiry(&claim), Some(expiry + 10u32.into()));
+	}
+
+	revoke_claim {
+		let l in 1 .. T::MaxProofLength::get() as u32;
+		let owner: T::AccountId = account("owner", 0, 0);
+		let caller = funded_caller::<T>();
+		let claim = vec![0u8; l as usize];
+		shared_claim::<T>(&owner, &caller, &claim, Some(ClaimAction::Revoke));
+		Module::<T>::set_claim_metadata(RawOrigin::Signed(owner).into(), claim.clone(), Some(full_metadata::<T>()))?;
+		Module::<T>::renew_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone(), Some(T::BlockNumber::max_value()))?;
+	}: _(RawOrigin::Signed(caller), claim.clone())
+	verify {
+		assert!(Module::<T>::proofs(&claim).is_none());
+	}
+
+	transfer_claim {
+		let l in 1 .. T::MaxProofLength::get() as u32;
+		let owner: T::AccountId = account("owner", 0, 0);
+		let caller = funded_caller::<T>();
+		let dest: T::AccountId = account("dest", 0, 0);
+		let claim = vec![0u8; l as usize];
+		shared_claim::<T>(&owner, &caller, &claim, Some(ClaimAction::Transfer(dest.clone())));
+	}: _(RawOrigin::Signed(caller), claim.clone(), dest.clone())
+	verify {
+		assert_eq!(Module::<T>::proofs(&claim).map(|proof| proof.owners), Some(vec![dest]));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create_claim::<Test>());
+			assert_ok!(test_benchmark_create_digest_claim::<Test>());
+			assert_ok!(test_benchmark_create_batch_claim::<Test>());
+			assert_ok!(test_benchmark_create_shared_claim::<Test>());
+			assert_ok!(test_benchmark_create_claim_with_details::<Test>());
+			assert_ok!(test_benchmark_set_claim_metadata::<Test>());
+			assert_ok!(test_benchmark_renew_claim::<Test>());
+			assert_ok!(test_benchmark_revoke_claim::<Test>());
+			assert_ok!(test_benchmark_transfer_claim::<Test>());
+		});
+	}
+}
@@ -1,12 +1,39 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-/// Edit this file to define custom logic or remove it if it is not needed.
-/// Learn more about FRAME and the core library of Substrate FRAME pallets:
-/// https://substrate.dev/docs/en/knowledgebase/runtime/frame
+//! Proof of existence: anchor a claim on chain with the account that owns it and the block it was
+//! created in.
+//!
+//! A claim has a single owner, or several co-owners of which a threshold has to approve before it is
+//! revoked or transferred. Claims change hands with `transfer_claim`, the new owner owns it alone.
+//!
+//! A claim is at most `MaxProofLength` bytes. With `DigestClaimsOnly` every claim has to be a
+//! `TaggedDigest`, the hash algorithm and the 32 byte digest of a document, so a document can not
+//! be notarised again under another encoding of its hash.
+//!
+//! A claim can carry a description, a MIME type and the hash of an external URI, and can expire.
+//! An expired claim lapses: it no longer counts as existing, and is removed in `on_initialize` with
+//! a budget of `LapseCleanupLimit` or when it is claimed again. Owners keep it alive with `renew_claim`.
+//!
+//! Creating a claim reserves `DepositPerByte` for every byte of the claim and of its encoded metadata
+//! from the creator, who gets it back when the claim is revoked or lapses. An owner that changes the
+//! metadata takes over the deposit at the new size. Root can set a flat notarisation fee, paid on
+//! every new claim to the account it is set with.
+//!
+//! `create_batch_claim` notarises many documents at once under the root of a Merkle tree of their
+//! digests, see `merkle`. The root is claimed as a `HashAlgo::BatchRoot` `TaggedDigest`, which no other
+//! call accepts, and owned, revoked and transferred like any other claim. `verify_batch_leaf` checks that a document is in a batch.
 
-use frame_support::{decl_module, decl_storage, decl_event, decl_error, dispatch, traits::Get};
-use frame_system::ensure_signed;
-use sp_std::prelude::*;  // 使用了Vec
+use codec::{Encode, Decode, DecodeAll};
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, ensure, dispatch, transactional,
+	storage::IterableStorageMap,
+	traits::{Get, Currency, ReservableCurrency, ExistenceRequirement},
+	weights::Weight,
+};
+use sp_runtime::traits::{One, Saturating, Zero};
+use frame_system::{ensure_signed, ensure_root};
+use sp_runtime::RuntimeDebug;
+use sp_std::{prelude::*, mem};
 
 #[cfg(test)]
 mod mock;
@@ -14,134 +41,564 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod merkle;
+pub mod weights;
+pub use weights::WeightInfo;
+
+/// Version of the pallet's storage, bumped whenever existing entries need a migration.
+const STORAGE_VERSION: u32 = 1;
+
 /// Configure the pallet by specifying the parameters and types on which it depends.
 pub trait Config: frame_system::Config {
 	/// Because this pallet emits events, it depends on the runtime's definition of an event.
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
+	/// Maximum number of co-owners of a claim.
+	type MaxOwners: Get<u32>;
+	/// Maximum length in bytes of a claim.
+	type MaxProofLength: Get<u8>;
+	/// Only accept claims that are an encoded `TaggedDigest`.
+	type DigestClaimsOnly: Get<bool>;
+	/// Maximum length in bytes of the description of a claim.
+	type MaxDescriptionLength: Get<u32>;
+	/// Maximum length in bytes of the MIME type of a claim.
+	type MaxMimeTypeLength: Get<u32>;
+	/// Most blocks and lapsed claims `on_initialize` goes through in a block.
+	type LapseCleanupLimit: Get<u32>;
+	/// Currency the deposits are reserved in and the notarisation fee is paid in.
+	type Currency: ReservableCurrency<Self::AccountId>;
+	/// Deposit reserved for every byte of a claim.
+	type DepositPerByte: Get<BalanceOf<Self>>;
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Hash algorithm of a tagged digest.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum HashAlgo {
+	Sha256,
+	Blake2_256,
+	Keccak256,
+	/// Root of the Merkle tree of a batch claim, only claimed by `create_batch_claim`.
+	BatchRoot,
+}
+
+/// A document notarised by its digest, encoded into the 33 bytes of the claim.
+pub type TaggedDigest = (HashAlgo, [u8; 32]);
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Proof<AccountId, BlockNumber> {
+	/// Sorted and without duplicates, a claim created with `create_claim` has a single owner.
+	pub owners: Vec<AccountId>,
+	/// Number of owners that have to approve a revocation or a transfer.
+	pub threshold: u32,
+	pub block: BlockNumber,
 }
-///设置长度上限，超过长度时返回错误
-pub trait Trait:frame_system::Trait{
-	type Event: From<Event<self>> + Into<<self as frame_system::Trait>::Event>;
-	type MaxProofLength:Get<u8>;
+
+impl<AccountId: Ord, BlockNumber> Proof<AccountId, BlockNumber> {
+	fn is_owner(&self, who: &AccountId) -> bool {
+		self.owners.binary_search(who).is_ok()
+	}
 }
 
-// The pallet's runtime storage items.
-// https://substrate.dev/docs/en/knowledgebase/runtime/storage
+/// What a claim is about.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct ClaimMetadata {
+	pub description: Vec<u8>,
+	pub mime_type: Vec<u8>,
+	/// Hash of the URI the document can be found at.
+	pub uri_hash: Option<[u8; 32]>,
+}
+
+/// What the owners of a claim approve.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum ClaimAction<AccountId> {
+	Revoke,
+	/// Transfer the claim to a single new owner.
+	Transfer(AccountId),
+}
+
+type ProofOf<T> = Proof<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>;
+
 decl_storage! {
-	// A unique name is used to ensure that the pallet's storage items are isolated.
-	// This name may be updated, but each pallet in the runtime must use a unique name.
-	// ---------------------------------vvvvvvvvvvvvvv
-	trait Store for Module<T: Trait> as TemplateModule {
-		Proofs get(fn proofs): map hasher(blake2_128_concat) Vec<u8> => (T::AccountId,T::BlockNumber)
+	// The prefix of the template pallet is kept, existing claims are stored under it.
+	trait Store for Module<T: Config> as TemplateModule {
+		Proofs get(fn proofs): map hasher(blake2_128_concat) Vec<u8> => Option<ProofOf<T>>;
+		/// Actions the owners of a shared claim approved, at most a revocation and a transfer per owner.
+		/// Cleared when the claim is revoked or transferred.
+		Approvals get(fn approvals): map hasher(blake2_128_concat) Vec<u8> => Vec<(T::AccountId, ClaimAction<T::AccountId>)>;
+		StorageVersion get(fn storage_version): u32;
+
+		Metadata get(fn metadata): map hasher(blake2_128_concat) Vec<u8> => Option<ClaimMetadata>;
+		/// Last block a claim is valid in, claims without an entry do not expire.
+		Expiries get(fn expiry): map hasher(blake2_128_concat) Vec<u8> => Option<T::BlockNumber>;
+		/// Claims to remove from a block on, the block after their expiry. A claim is only listed under its current expiry.
+		ClaimsLapsingAt get(fn claims_lapsing_at): map hasher(twox_64_concat) T::BlockNumber => Vec<Vec<u8>>;
+		/// First block of `ClaimsLapsingAt` that can still hold claims to remove.
+		NextLapseBlock get(fn next_lapse_block): T::BlockNumber;
+
+		/// Account that reserved the deposit of a claim and the amount, refunded when the claim is removed.
+		Deposits get(fn deposit): map hasher(blake2_128_concat) Vec<u8> => Option<(T::AccountId, BalanceOf<T>)>;
+		/// Account the notarisation fee is paid to and the fee, no fee is charged without it.
+		NotarisationFee get(fn notarisation_fee): Option<(T::AccountId, BalanceOf<T>)>;
+
+		/// Number of leaves of the Merkle tree of a batch claim.
+		BatchLeafCounts get(fn batch_leaf_count): map hasher(blake2_128_concat) Vec<u8> => Option<u32>;
 	}
 }
 
-// Pallets use events to inform users when important changes are made.
-// https://substrate.dev/docs/en/knowledgebase/runtime/events
 decl_event!(
-	pub enum Event<T> where AccountId = <T as system::Trait>::AccountId {
-		ClaimCreated(AccountId,Vec<u8>),  // 用户AccountId，存证内容 Vec<u8>
-		ClaimRevoked(AccountId,Vec<u8>),
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Config>::AccountId,
+		BlockNumber = <T as frame_system::Config>::BlockNumber,
+		Balance = BalanceOf<T>,
+	{
+		/// A claim is created. \[owner, claim\]
+		ClaimCreated(AccountId, Vec<u8>),
+		/// A claim of the Merkle root of a batch of documents is created. \[owner, claim, leaf_count\]
+		BatchClaimCreated(AccountId, Vec<u8>, u32),
+		/// A claim is created with several owners. \[creator, claim, threshold\]
+		SharedClaimCreated(AccountId, Vec<u8>, u32),
+		/// A claim is revoked. \[owner, claim\]
+		ClaimRevoked(AccountId, Vec<u8>),
+		/// A claim is transferred. \[from, to, claim\]
+		ClaimTransferred(AccountId, AccountId, Vec<u8>),
+		/// An owner of a shared claim approved an action below the threshold. \[owner, claim, action, approvals\]
+		ActionApproved(AccountId, Vec<u8>, ClaimAction<AccountId>, u32),
+		/// The metadata of a claim is set or cleared. \[owner, claim\]
+		MetadataSet(AccountId, Vec<u8>),
+		/// The expiry of a claim changed, `None` if it no longer expires. \[owner, claim, expiry\]
+		ClaimRenewed(AccountId, Vec<u8>, Option<BlockNumber>),
+		/// An expired claim is removed. \[claim\]
+		ClaimLapsed(Vec<u8>),
+		/// The notarisation fee of a claim is paid. \[payer, receiver, fee\]
+		NotarisationFeePaid(AccountId, AccountId, Balance),
 	}
 );
 
-// Errors inform users that something went wrong.
 decl_error! {
-pub enum Error for Module<T: Trait> {
-		ProofAlreadyExist,    // 存在异常，即存证已经存在
+	pub enum Error for Module<T: Config> {
+		ProofAlreadyExist,
+		/// The claim is longer than `MaxProofLength`.
+		ProofTooLong,
+		/// Only tagged digests are accepted as claims.
+		NotTaggedDigest,
 		ClaimNotExist,
 		NotClaimOwner,
+		/// A shared claim has more than `MaxOwners` owners.
+		TooManyOwners,
+		/// The threshold is zero or above the number of owners.
+		InvalidThreshold,
+		/// The owner already approved this action.
+		AlreadyApproved,
+		DescriptionTooLong,
+		MimeTypeTooLong,
+		/// The expiry of a claim has to be a future block.
+		ExpiryInPast,
+		/// A batch claim needs at least one leaf.
+		EmptyBatch,
+		/// Batch roots are only claimed by `create_batch_claim`.
+		BatchRootClaim,
 	}
 }
 
-// Dispatchable functions allows users to interact with the pallet and invoke state changes.
-// These functions materialize as "extrinsics", which are often compared to transactions.
-// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
 decl_module! {
 	pub struct Module<T: Config> for enum Call where origin: T::Origin {
-		// Errors must be initialized if they are used by the pallet.
 		type Error = Error<T>;
 
-		// Events must be initialized if they are used by the pallet.
+		const MaxOwners: u32 = T::MaxOwners::get();
+		const MaxProofLength: u8 = T::MaxProofLength::get();
+		const DigestClaimsOnly: bool = T::DigestClaimsOnly::get();
+		const MaxDescriptionLength: u32 = T::MaxDescriptionLength::get();
+		const MaxMimeTypeLength: u32 = T::MaxMimeTypeLength::get();
+		const LapseCleanupLimit: u32 = T::LapseCleanupLimit::get();
+		const DepositPerByte: BalanceOf<T> = T::DepositPerByte::get();
+
 		fn deposit_event() = default;
 
-		/// An example dispatchable that takes a singles value as a parameter, writes the value to
-		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
-		#[weight = 10_000 + T::DbWeight::get().writes(1)]
-		pub fn do_something(origin, something: u32) -> dispatch::DispatchResult {
-			// Check that the extrinsic was signed and get the signer.
-			// This function will return an error if the extrinsic is not signed.
-			// https://substrate.dev/docs/en/knowledgebase/runtime/origin
-			let who = ensure_signed(origin)?;
-
-			// Update storage.
-			Something::put(something);
-
-			// Emit an event.
-			Self::deposit_event(RawEvent::SomethingStored(something, who));
-			// Return a successful DispatchResult
+		fn on_runtime_upgrade() -> Weight {
+			if Self::storage_version() < 1 {
+				let migrated = Self::migrate_proofs();
+				StorageVersion::put(STORAGE_VERSION);
+				return T::DbWeight::get().reads_writes(migrated + 1, migrated + 1);
+			}
+			T::DbWeight::get().reads(1)
+		}
+
+		/// Removes lapsed claims, going through at most `LapseCleanupLimit` blocks and claims.
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let mut budget = T::LapseCleanupLimit::get();
+			let mut block = Self::next_lapse_block();
+			// Nothing lapses before the first cleanup, no need to go through earlier blocks.
+			if block.is_zero() {
+				block = n;
+			}
+
+			while block <= n && budget > 0 {
+				budget -= 1;
+				let mut claims = ClaimsLapsingAt::<T>::take(block);
+				while budget > 0 {
+					let claim = match claims.pop() {
+						Some(claim) => claim,
+						None => break,
+					};
+					budget -= 1;
+					if Self::expiry(&claim) == Some(block - One::one()) {
+						Self::remove_claim(&claim);
+						Self::deposit_event(RawEvent::ClaimLapsed(claim));
+					}
+				}
+				if !claims.is_empty() {
+					ClaimsLapsingAt::<T>::insert(block, claims);
+					break;
+				}
+				block += One::one();
+			}
+			NextLapseBlock::<T>::put(block);
+
+			let used = (T::LapseCleanupLimit::get() - budget) as Weight;
+			T::DbWeight::get().reads_writes(1 + 4 * used, 1 + 7 * used)
+		}
+
+		/// Creates a claim owned by the sender, reserving the deposit and paying the notarisation fee.
+		#[weight = T::WeightInfo::create_claim(claim.len() as u32)]
+		#[transactional]
+		pub fn create_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_new_claim(&claim)?;
+			Self::charge(&sender, &claim, None)?;
+
+			Proofs::<T>::insert(&claim, Proof {
+				owners: vec![sender.clone()],
+				threshold: 1,
+				block: frame_system::Module::<T>::block_number(),
+			});
+
+			Self::deposit_event(RawEvent::ClaimCreated(sender, claim));
+			Ok(())
+		}
+
+		/// Creates a claim of the digest of a document, under the encoded `TaggedDigest` as claim.
+		#[weight = T::WeightInfo::create_digest_claim()]
+		#[transactional]
+		pub fn create_digest_claim(origin, algo: HashAlgo, digest: [u8; 32]) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let claim = (algo, digest).encode();
+			Self::ensure_new_claim(&claim)?;
+			Self::charge(&sender, &claim, None)?;
+
+			Proofs::<T>::insert(&claim, Proof {
+				owners: vec![sender.clone()],
+				threshold: 1,
+				block: frame_system::Module::<T>::block_number(),
+			});
+
+			Self::deposit_event(RawEvent::ClaimCreated(sender, claim));
+			Ok(())
+		}
+
+		/// Creates a claim of `root`, the Merkle root of the digests of `leaf_count` documents.
+		#[weight = T::WeightInfo::create_batch_claim()]
+		#[transactional]
+		pub fn create_batch_claim(origin, root: [u8; 32], leaf_count: u32) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(leaf_count > 0, Error::<T>::EmptyBatch);
+			let claim = Self::batch_claim(root);
+			Self::ensure_claimable(&claim)?;
+			Self::charge(&sender, &claim, None)?;
+
+			Proofs::<T>::insert(&claim, Proof {
+				owners: vec![sender.clone()],
+				threshold: 1,
+				block: frame_system::Module::<T>::block_number(),
+			});
+			BatchLeafCounts::insert(&claim, leaf_count);
+
+			Self::deposit_event(RawEvent::BatchClaimCreated(sender, claim, leaf_count));
 			Ok(())
 		}
 
-		/// An example dispatchable that may throw a custom error.
-		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
-		pub fn cause_error(origin) -> dispatch::DispatchResult {
-			let _who = ensure_signed(origin)?;
-
-			// Read a value from storage.
-			match Something::get() {
-				// Return an error if the value has not been set.
-				None => Err(Error::<T>::NoneValue)?,
-				Some(old) => {
-					// Increment the value read from storage; will error in the event of overflow.
-					let new = old.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
-					// Update the value in storage with the incremented result.
-					Something::put(new);
-					Ok(())
-				},
+		/// Creates a claim owned by `owners`, the sender among them. `threshold` of them have to
+		/// approve before it is revoked or transferred. The sender pays the deposit and gets it back.
+		#[weight = T::WeightInfo::create_shared_claim(claim.len() as u32, owners.len() as u32)]
+		#[transactional]
+		pub fn create_shared_claim(origin, claim: Vec<u8>, owners: Vec<T::AccountId>, threshold: u32) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_new_claim(&claim)?;
+
+			let mut owners = owners;
+			owners.sort();
+			owners.dedup();
+			ensure!(owners.len() as u32 <= T::MaxOwners::get(), Error::<T>::TooManyOwners);
+			ensure!(threshold > 0 && threshold as usize <= owners.len(), Error::<T>::InvalidThreshold);
+			let proof = Proof { owners, threshold, block: frame_system::Module::<T>::block_number() };
+			ensure!(proof.is_owner(&sender), Error::<T>::NotClaimOwner);
+			Self::charge(&sender, &claim, None)?;
+
+			Proofs::<T>::insert(&claim, proof);
+
+			Self::deposit_event(RawEvent::SharedClaimCreated(sender, claim, threshold));
+			Ok(())
+		}
+
+		/// Creates a claim with metadata that is valid up to and including block `expiry`.
+		#[weight = T::WeightInfo::create_claim_with_details(claim.len() as u32)]
+		#[transactional]
+		pub fn create_claim_with_details(
+			origin,
+			claim: Vec<u8>,
+			metadata: Option<ClaimMetadata>,
+			expiry: Option<T::BlockNumber>,
+		) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_new_claim(&claim)?;
+			if let Some(ref metadata) = metadata {
+				Self::ensure_valid_metadata(metadata)?;
+			}
+			if let Some(expiry) = expiry {
+				ensure!(expiry > frame_system::Module::<T>::block_number(), Error::<T>::ExpiryInPast);
 			}
+			Self::charge(&sender, &claim, metadata.as_ref())?;
+
+			Proofs::<T>::insert(&claim, Proof {
+				owners: vec![sender.clone()],
+				threshold: 1,
+				block: frame_system::Module::<T>::block_number(),
+			});
+			if let Some(metadata) = metadata {
+				Metadata::insert(&claim, metadata);
+			}
+			Self::set_expiry(&claim, expiry);
+
+			Self::deposit_event(RawEvent::ClaimCreated(sender, claim));
+			Ok(())
 		}
-		
-		 // 创建存证，创建存证需要有两个关键参数：交易发送方origin，存证hash值claim，由于存证hash函数未知，也和decl_storage定义对应，这里使用变长Vec<u8>
-        #[weight = 0]
-		pub fn create_claim(origin,claim:Vec<u8>)->dispatch::DispatchResult{
-			// 做必要检查，检查内容： 1，交易发送方是不是一个签名的用户 2，存证是否被别人创建过，创建过就抛出错误
-			// 首先去创建签名交易，通过ensure_signed这样的system提供的版本方法来校验
-			let sender = ensure_signed(origin)?;  // 存证拥有人是交易发送方，只有拥有人才可以调用存证，sender即当前交易发送方
-  			// 如果存在存证，返回错误 ProofAlreadyExist
-  			// ps:ensure!宏是确保表达式中的结果为true，这里取反操作
-			ensure!(!Proofs::<T>::contains_key(&claim),Error::<T>::ProofAlreadyExist);  // 这里用到一个错误  ProofAlreadyExist，该错误需要在decl_error声明
-			// 做insert操作，insert是key-value方式。这里的key-value是一个tuple
-			// 这个tuple的第一个元素是AccountId；第二个是当前交易所处的区块，使用系统模块提供的block_number工具方法获取
-			Proofs::<T>::insert(&claim,(sender.clone(),system::Module::<T>::block_number()));  // 插入操作
-			// 触发一个event来通知客户端，RawEvent由宏生成；   sender:存在拥有人；claim:存在hash值 通过event通知客户端
-			Self::deposit_event(RawEvent::ClaimCreated(sender,claim));   // ClaimCreated事件，需要decl_event处理
-			// 返回ok
+
+		/// Sets the metadata of a claim, or clears it with `None`. Any owner of a shared claim can set it,
+		/// the sender reserves the deposit of the claim with its new metadata and the previous one is refunded.
+		#[weight = T::WeightInfo::set_claim_metadata(claim.len() as u32)]
+		#[transactional]
+		pub fn set_claim_metadata(origin, claim: Vec<u8>, metadata: Option<ClaimMetadata>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let proof = Self::live_proof(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+			ensure!(proof.is_owner(&sender), Error::<T>::NotClaimOwner);
+			if let Some(ref metadata) = metadata {
+				Self::ensure_valid_metadata(metadata)?;
+			}
+			Self::reserve_deposit(&sender, &claim, metadata.as_ref())?;
+
+			match metadata {
+				Some(metadata) => Metadata::insert(&claim, metadata),
+				None => Metadata::remove(&claim),
+			}
+
+			Self::deposit_event(RawEvent::MetadataSet(sender, claim));
 			Ok(())
+		}
+
+		/// Moves the expiry of a claim that has not lapsed yet to `expiry`, `None` to keep it
+		/// forever. Any owner of a shared claim can renew it.
+		#[weight = T::WeightInfo::renew_claim(claim.len() as u32)]
+		pub fn renew_claim(origin, claim: Vec<u8>, expiry: Option<T::BlockNumber>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let proof = Self::live_proof(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+			ensure!(proof.is_owner(&sender), Error::<T>::NotClaimOwner);
+			if let Some(expiry) = expiry {
+				ensure!(expiry > frame_system::Module::<T>::block_number(), Error::<T>::ExpiryInPast);
+			}
+
+			Self::set_expiry(&claim, expiry);
 
+			Self::deposit_event(RawEvent::ClaimRenewed(sender, claim, expiry));
+			Ok(())
 		}
-		
-		#[weight = 0]
-		pub fn revoke_claim(origin,claim: Vec<u8>) -> dispatch::DispatchResult{
-			let sender = ensure_signed(origin)?;  // 交易发送方式已签名的， 存证拥有人是交易发送方，只有拥有人才可以吊销存证
 
-  			// 判断存储单元里面是存在这样一个存证；如果不存在，抛出错误，错误我们叫ClaimNotExist
-			ensure!(Proofs::<T>::contains_key(&claim),Error::<T>::ClaimNotExist);
+		/// Revokes a claim. An owner of a shared claim approves the revocation, the claim is revoked
+		/// once the threshold is reached, and the deposit is refunded.
+		#[weight = T::WeightInfo::revoke_claim(claim.len() as u32)]
+		pub fn revoke_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			if !Self::approve(&sender, &claim, ClaimAction::Revoke)? {
+				return Ok(());
+			}
 
-			// 获取这样的存证  owner: accountId   block_number
-			let (owner,_block_number) = Proofs::<T>::get(&claim);  // 通过get api获取这样的一个存证
+			Self::remove_claim(&claim);
 
-			ensure!(owner == sender,Error::<T>::NotClaimOwner);  // 确保交易发送方是我们的存证人，如果不是，返回Error，这个Error我们叫NotClaimOwner
+			Self::deposit_event(RawEvent::ClaimRevoked(sender, claim));
+			Ok(())
+		}
 
-			// 以上校验完成之后，我们就可以删除我们的存证
-		    // 存储向上调用remove函数进行删除
-		    Proofs::<T>::remove(&claim);
+		/// Transfers a claim to `dest`, its only owner from then on. An owner of a shared claim
+		/// approves the transfer, the claim is transferred once the threshold is reached.
+		#[weight = T::WeightInfo::transfer_claim(claim.len() as u32)]
+		pub fn transfer_claim(origin, claim: Vec<u8>, dest: T::AccountId) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			if !Self::approve(&sender, &claim, ClaimAction::Transfer(dest.clone()))? {
+				return Ok(());
+			}
 
-			// 触发一个事件，返回存证人和hash
-		    Self::deposit_event(RawEvent::ClaimRevoked(sender,claim));
+			Proofs::<T>::mutate(&claim, |proof| if let Some(proof) = proof {
+				proof.owners = vec![dest.clone()];
+				proof.threshold = 1;
+			});
+			Approvals::<T>::remove(&claim);
 
-			// 返回
+			Self::deposit_event(RawEvent::ClaimTransferred(sender, dest, claim));
 			Ok(())
 		}
+
+		/// Sets the notarisation fee paid to `receiver`, `None` to notarise without a fee.
+		#[weight = T::DbWeight::get().writes(1)]
+		pub fn set_notarisation_fee(origin, fee: Option<(T::AccountId, BalanceOf<T>)>) -> dispatch::DispatchResult {
+			ensure_root(origin)?;
+			NotarisationFee::<T>::set(fee);
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Module<T> {
+	/// The proof of a claim, `None` if it does not exist or lapsed.
+	pub fn live_proof(claim: &[u8]) -> Option<ProofOf<T>> {
+		let proof = Self::proofs(claim)?;
+		match Self::expiry(claim) {
+			Some(expiry) if expiry < frame_system::Module::<T>::block_number() => None,
+			_ => Some(proof),
+		}
+	}
+
+	/// The claim a batch with Merkle root `root` is stored under.
+	pub fn batch_claim(root: [u8; 32]) -> Vec<u8> {
+		(HashAlgo::BatchRoot, root).encode()
+	}
+
+	/// Whether `leaf` is at `index` of the live batch claim of `root`, as proven by `proof`.
+	pub fn verify_batch_leaf(root: [u8; 32], leaf: [u8; 32], index: u32, proof: Vec<[u8; 32]>) -> bool {
+		let claim = Self::batch_claim(root);
+		let leaf_count = match Self::batch_leaf_count(&claim) {
+			Some(leaf_count) if Self::live_proof(&claim).is_some() => leaf_count,
+			_ => return false,
+		};
+		merkle::root_from_proof(&leaf, index, leaf_count, &proof) == Some(root)
+	}
+
+	fn ensure_new_claim(claim: &[u8]) -> Result<(), Error<T>> {
+		// Otherwise a digest could be claimed under the root of a batch before the batch is.
+		ensure!(
+			!matches!(TaggedDigest::decode_all(claim), Ok((HashAlgo::BatchRoot, _))),
+			Error::<T>::BatchRootClaim
+		);
+		Self::ensure_claimable(claim)
+	}
+
+	/// Checks the length and encoding of a claim that does not exist, removing it if it lapsed.
+	fn ensure_claimable(claim: &[u8]) -> Result<(), Error<T>> {
+		ensure!(claim.len() <= T::MaxProofLength::get() as usize, Error::<T>::ProofTooLong);
+		if T::DigestClaimsOnly::get() {
+			TaggedDigest::decode_all(claim).map_err(|_| Error::<T>::NotTaggedDigest)?;
+		}
+		if Proofs::<T>::contains_key(claim) {
+			ensure!(Self::live_proof(claim).is_none(), Error::<T>::ProofAlreadyExist);
+			// A lapsed claim that was not cleaned up yet can be claimed again.
+			Self::remove_claim(claim);
+			Self::deposit_event(RawEvent::ClaimLapsed(claim.to_vec()));
+		}
+		Ok(())
+	}
+
+	fn ensure_valid_metadata(metadata: &ClaimMetadata) -> Result<(), Error<T>> {
+		ensure!(metadata.description.len() as u32 <= T::MaxDescriptionLength::get(), Error::<T>::DescriptionTooLong);
+		ensure!(metadata.mime_type.len() as u32 <= T::MaxMimeTypeLength::get(), Error::<T>::MimeTypeTooLong);
+		Ok(())
+	}
+
+	/// Sets the expiry of a claim, moving it from the lapse list of its previous expiry to that of the new one.
+	fn set_expiry(claim: &[u8], expiry: Option<T::BlockNumber>) {
+		if let Some(previous) = Expiries::<T>::take(claim) {
+			ClaimsLapsingAt::<T>::mutate_exists(previous.saturating_add(One::one()), |claims| {
+				if let Some(list) = claims {
+					list.retain(|listed| listed[..] != claim[..]);
+					if list.is_empty() {
+						*claims = None;
+					}
+				}
+			});
+		}
+		if let Some(expiry) = expiry {
+			Expiries::<T>::insert(claim, expiry);
+			ClaimsLapsingAt::<T>::append(expiry.saturating_add(One::one()), claim.to_vec());
+		}
+	}
+
+	fn remove_claim(claim: &[u8]) {
+		Proofs::<T>::remove(claim);
+		Approvals::<T>::remove(claim);
+		Metadata::remove(claim);
+		Self::set_expiry(claim, None);
+		BatchLeafCounts::remove(claim);
+		if let Some((depositor, deposit)) = Deposits::<T>::take(claim) {
+			T::Currency::unreserve(&depositor, deposit);
+		}
+	}
+
+	/// Reserves the deposit of a new claim with `metadata` from `who` and pays the notarisation fee.
+	fn charge(who: &T::AccountId, claim: &[u8], metadata: Option<&ClaimMetadata>) -> dispatch::DispatchResult {
+		Self::reserve_deposit(who, claim, metadata)?;
+
+		if let Some((receiver, fee)) = Self::notarisation_fee() {
+			T::Currency::transfer(who, &receiver, fee, ExistenceRequirement::KeepAlive)?;
+			Self::deposit_event(RawEvent::NotarisationFeePaid(who.clone(), receiver, fee));
+		}
+		Ok(())
+	}
+
+	/// Reserves `DepositPerByte` for every byte of `claim` and of its encoded `metadata` from `who`,
+	/// refunding the deposit reserved for the claim before.
+	fn reserve_deposit(who: &T::AccountId, claim: &[u8], metadata: Option<&ClaimMetadata>) -> dispatch::DispatchResult {
+		if let Some((depositor, deposit)) = Deposits::<T>::take(claim) {
+			T::Currency::unreserve(&depositor, deposit);
+		}
+		let bytes = claim.len() + metadata.map_or(0, |metadata| metadata.encode().len());
+		let deposit = T::DepositPerByte::get().saturating_mul((bytes as u32).into());
+		T::Currency::reserve(who, deposit)?;
+		Deposits::<T>::insert(claim, (who.clone(), deposit));
+		Ok(())
+	}
+
+	/// Records the approval of `action` on `claim` by `who`, true once enough owners approved it.
+	fn approve(who: &T::AccountId, claim: &[u8], action: ClaimAction<T::AccountId>) -> Result<bool, Error<T>> {
+		let proof = Self::live_proof(claim).ok_or(Error::<T>::ClaimNotExist)?;
+		ensure!(proof.is_owner(who), Error::<T>::NotClaimOwner);
+		if proof.threshold <= 1 {
+			return Ok(true);
+		}
+
+		let mut approvals = Self::approvals(claim);
+		ensure!(!approvals.iter().any(|(owner, approved)| owner == who && *approved == action), Error::<T>::AlreadyApproved);
+		// Approving a transfer withdraws the owner's approval of a transfer to someone else.
+		approvals.retain(|(owner, approved)| owner != who || mem::discriminant(approved) != mem::discriminant(&action));
+		approvals.push((who.clone(), action.clone()));
+		let count = approvals.iter().filter(|(_, approved)| *approved == action).count() as u32;
+		if count >= proof.threshold {
+			return Ok(true);
+		}
+
+		Approvals::<T>::insert(claim, approvals);
+		Self::deposit_event(RawEvent::ActionApproved(who.clone(), claim.to_vec(), action, count));
+		Ok(false)
+	}
+
+	/// Converts the `(owner, block)` proofs of storage version 0, returns the number of proofs.
+	fn migrate_proofs() -> Weight {
+		let mut migrated: Weight = 0;
+		Proofs::<T>::translate::<(T::AccountId, T::BlockNumber), _>(|_claim, (owner, block)| {
+			migrated += 1;
+			Some(Proof { owners: vec![owner], threshold: 1, block })
+		});
+		migrated
 	}
 }
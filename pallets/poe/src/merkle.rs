@@ -0,0 +1,57 @@
+//! Merkle trees of batch claims.
+//!
+//! The leaves are the 32 byte digests of the notarised documents. A leaf is hashed as
+//! `blake2_256(0x00 ++ leaf)` and an inner node as `blake2_256(0x01 ++ left ++ right)`, so a leaf
+//! can not pass for an inner node. The last node of a level with an odd number of nodes is moved
+//! up to the next level unchanged.
+//!
+//! A proof lists the siblings of the path from a leaf to the root, from the leaves up. Whether a
+//! sibling is on the left or the right follows from the index of the leaf and the number of leaves.
+
+use sp_io::hashing::blake2_256;
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+/// Hash of a leaf of the tree.
+pub fn leaf_hash(leaf: &[u8; 32]) -> [u8; 32] {
+	let mut input = [0u8; 33];
+	input[0] = LEAF_PREFIX;
+	input[1..].copy_from_slice(leaf);
+	blake2_256(&input)
+}
+
+/// Hash of an inner node of the tree.
+pub fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+	let mut input = [0u8; 65];
+	input[0] = NODE_PREFIX;
+	input[1..33].copy_from_slice(left);
+	input[33..].copy_from_slice(right);
+	blake2_256(&input)
+}
+
+/// Root of the tree of `leaf_count` leaves that `proof` proves `leaf` at `index` to be in, `None`
+/// if the proof does not fit the shape of such a tree.
+pub fn root_from_proof(leaf: &[u8; 32], index: u32, leaf_count: u32, proof: &[[u8; 32]]) -> Option<[u8; 32]> {
+	if index >= leaf_count {
+		return None;
+	}
+
+	let mut siblings = proof.iter();
+	let mut hash = leaf_hash(leaf);
+	let (mut index, mut width) = (index, leaf_count);
+	while width > 1 {
+		if index % 2 == 1 {
+			hash = node_hash(siblings.next()?, &hash);
+		} else if index + 1 < width {
+			hash = node_hash(&hash, siblings.next()?);
+		}
+		index /= 2;
+		width = width / 2 + width % 2;
+	}
+
+	match siblings.next() {
+		Some(_) => None,
+		None => Some(hash),
+	}
+}
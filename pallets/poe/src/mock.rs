@@ -16,6 +16,14 @@ impl_outer_origin! {
 pub struct Test;
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
+	pub const MaxOwners: u32 = 3;
+	pub const MaxProofLength: u8 = 40;
+	pub storage DigestClaimsOnly: bool = false;
+	pub const MaxDescriptionLength: u32 = 16;
+	pub const MaxMimeTypeLength: u32 = 16;
+	pub const LapseCleanupLimit: u32 = 4;
+	pub const ExistentialDeposit: u64 = 1;
+	pub const DepositPerByte: u64 = 1;
 }
 
 impl system::Config for Test {
@@ -36,19 +44,45 @@ impl system::Config for Test {
 	type BlockHashCount = BlockHashCount;
 	type Version = ();
 	type PalletInfo = ();
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
 }
 
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type Balance = u64;
+	type DustRemoval = ();
+	type Event = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
 impl Config for Test {
 	type Event = ();
+	type MaxOwners = MaxOwners;
+	type MaxProofLength = MaxProofLength;
+	type DigestClaimsOnly = DigestClaimsOnly;
+	type MaxDescriptionLength = MaxDescriptionLength;
+	type MaxMimeTypeLength = MaxMimeTypeLength;
+	type LapseCleanupLimit = LapseCleanupLimit;
+	type Currency = Balances;
+	type DepositPerByte = DepositPerByte;
+	type WeightInfo = ();
 }
 
+pub type System = system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
 pub type PoeModule = Module<Test>;
 
 // Build genesis storage according to the mock runtime.
+// Accounts 1, 2 and 3 start with 100, there is no notarisation fee.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 100), (2, 100), (3, 100)],
+	}.assimilate_storage(&mut t).unwrap();
+	t.into()
 }
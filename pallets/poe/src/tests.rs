@@ -1,56 +1,328 @@
 use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
+use frame_support::{assert_ok, assert_noop, storage::unhashed, traits::{OnInitialize, OnRuntimeUpgrade}};
 use super::*;
 
+fn proof(owners: Vec<u64>, threshold: u32) -> Option<Proof<u64, u64>> {
+	Some(Proof { owners, threshold, block: frame_system::Module::<Test>::block_number() })
+}
+
 #[test]
-fn creat_claim_works() {
+fn create_claim_works() {
 	new_test_ext().execute_with(|| {
-		new_test_ext().execute_with(execute:||{
-			let claim:Vec<i32>=vec![0,1]；
-			assert_ok!(PoeModule::create_claim(Origin::signed(1),claim.clone()));
-			assert_eq!(Proofs::<Test>::get(&claim),(1,frame_system::Module::<Test>::block_number()));
-		})
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim), proof(vec![1], 1));
 	});
 }
 
 #[test]
-fn creat_claim_failed_when_claim_already_exist() {
+fn create_claim_failed_when_claim_already_exist() {
 	new_test_ext().execute_with(|| {
-		new_test_ext().execute_with(execute:||{
-			let claim:Vec<i32>=vec![0,1]；
-			let _=PoeModule::create_claim(Origin::signed(1),claim.clone());
+		let claim = vec![0, 1];
+		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
 
-			assert_noop!(
-				PoeModule::create_claim(Origin::signed(1),claim.clone()),
-				Error::<Test>::ProofAlreadyExist
-			);
-		})
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(1), claim.clone()),
+			Error::<Test>::ProofAlreadyExist
+		);
 	});
 }
 
 #[test]
 fn revoke_claim_works() {
 	new_test_ext().execute_with(|| {
-		new_test_ext().execute_with(execute:||{
-			let claim:Vec<i32>=vec![0,1]；
-			let _=PoeModule::create_claim(Origin::signed(1),claim.clone());
+		let claim = vec![0, 1];
+		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
+
+		assert_noop!(PoeModule::revoke_claim(Origin::signed(2), claim.clone()), Error::<Test>::NotClaimOwner);
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim), None);
+	});
+}
+
+#[test]
+fn revoke_claim_failed_when_claim_is_not_exist() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+
+		assert_noop!(
+			PoeModule::revoke_claim(Origin::signed(1), claim.clone()),
+			Error::<Test>::ClaimNotExist
+		);
+	});
+}
+
+#[test]
+fn transfer_claim_works() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		let _ = PoeModule::create_claim(Origin::signed(1), claim.clone());
+
+		assert_noop!(PoeModule::transfer_claim(Origin::signed(2), claim.clone(), 3), Error::<Test>::NotClaimOwner);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2));
+		assert_eq!(Proofs::<Test>::get(&claim), proof(vec![2], 1));
+
+		assert_noop!(PoeModule::revoke_claim(Origin::signed(1), claim.clone()), Error::<Test>::NotClaimOwner);
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(2), claim.clone()));
+	});
+}
+
+#[test]
+fn shared_claim_needs_threshold_to_revoke() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_noop!(
+			PoeModule::create_shared_claim(Origin::signed(1), claim.clone(), vec![1, 2, 3, 4], 2),
+			Error::<Test>::TooManyOwners
+		);
+		assert_noop!(
+			PoeModule::create_shared_claim(Origin::signed(1), claim.clone(), vec![1, 2], 3),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_noop!(
+			PoeModule::create_shared_claim(Origin::signed(4), claim.clone(), vec![1, 2], 2),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_ok!(PoeModule::create_shared_claim(Origin::signed(1), claim.clone(), vec![3, 1, 2, 1], 2));
+		assert_eq!(Proofs::<Test>::get(&claim), proof(vec![1, 2, 3], 2));
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(2), claim.clone()));
+		assert!(Proofs::<Test>::contains_key(&claim));
+		assert_noop!(PoeModule::revoke_claim(Origin::signed(2), claim.clone()), Error::<Test>::AlreadyApproved);
+		// Approvals of a transfer do not count towards the revocation.
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 4));
+		assert_eq!(Proofs::<Test>::get(&claim), proof(vec![1, 2, 3], 2));
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(3), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim), None);
+		assert!(Approvals::<Test>::get(&claim).is_empty());
+	});
+}
+
+#[test]
+fn owner_approves_one_transfer_at_a_time() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_shared_claim(Origin::signed(1), claim.clone(), vec![1, 2, 3], 2));
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 4));
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 5));
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(PoeModule::approvals(&claim), vec![(1, ClaimAction::Transfer(5)), (1, ClaimAction::Revoke)]);
+
+		// The approval of the transfer to 4 was withdrawn.
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(2), claim.clone(), 4));
+		assert_eq!(Proofs::<Test>::get(&claim), proof(vec![1, 2, 3], 2));
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(3), claim.clone(), 5));
+		assert_eq!(Proofs::<Test>::get(&claim), proof(vec![5], 1));
+		assert!(PoeModule::approvals(&claim).is_empty());
+	});
+}
+
+#[test]
+fn migration_converts_old_proofs() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		unhashed::put(&Proofs::<Test>::hashed_key_for(&claim), &(1u64, 5u64));
+
+		assert_eq!(PoeModule::storage_version(), 0);
+		<PoeModule as OnRuntimeUpgrade>::on_runtime_upgrade();
+
+		assert_eq!(PoeModule::storage_version(), STORAGE_VERSION);
+		assert_eq!(Proofs::<Test>::get(&claim), Some(Proof { owners: vec![1], threshold: 1, block: 5 }));
+	});
+}
+
+#[test]
+fn create_claim_failed_when_claim_too_long() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(PoeModule::create_claim(Origin::signed(1), vec![0; 41]), Error::<Test>::ProofTooLong);
+		assert_noop!(
+			PoeModule::create_shared_claim(Origin::signed(1), vec![0; 41], vec![1, 2], 1),
+			Error::<Test>::ProofTooLong
+		);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![0; 40]));
+	});
+}
+
+#[test]
+fn digest_claims_are_normalised() {
+	new_test_ext().execute_with(|| {
+		DigestClaimsOnly::set(&true);
+		let digest = [7; 32];
+
+		assert_noop!(PoeModule::create_claim(Origin::signed(1), digest.to_vec()), Error::<Test>::NotTaggedDigest);
+		assert_ok!(PoeModule::create_digest_claim(Origin::signed(1), HashAlgo::Sha256, digest));
+		// The same digest in its raw claim encoding is the same claim.
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(2), (HashAlgo::Sha256, digest).encode()),
+			Error::<Test>::ProofAlreadyExist
+		);
+		assert_ok!(PoeModule::create_digest_claim(Origin::signed(2), HashAlgo::Keccak256, digest));
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), (HashAlgo::Sha256, digest).encode()));
+	});
+}
+
+#[test]
+fn claim_carries_metadata() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		let metadata = ClaimMetadata { description: b"contract".to_vec(), mime_type: b"application/pdf".to_vec(), uri_hash: Some([1; 32]) };
+		assert_noop!(
+			PoeModule::create_claim_with_details(
+				Origin::signed(1),
+				claim.clone(),
+				Some(ClaimMetadata { description: vec![0; 17], ..metadata.clone() }),
+				None,
+			),
+			Error::<Test>::DescriptionTooLong
+		);
+		assert_ok!(PoeModule::create_claim_with_details(Origin::signed(1), claim.clone(), Some(metadata.clone()), None));
+		assert_eq!(PoeModule::metadata(&claim), Some(metadata));
+		// The 2 bytes of the claim and the 58 of the encoded metadata.
+		assert_eq!(Balances::reserved_balance(1), 60);
 
-			assert_ok!(PoeModule::create_claim(Origin::signed(1),claim.clone()));
-		})
+		assert_noop!(PoeModule::set_claim_metadata(Origin::signed(2), claim.clone(), None), Error::<Test>::NotClaimOwner);
+		let memo = ClaimMetadata { description: b"memo".to_vec(), ..Default::default() };
+		assert_ok!(PoeModule::set_claim_metadata(Origin::signed(1), claim.clone(), Some(memo)));
+		assert_eq!(Balances::reserved_balance(1), 9);
+		assert_ok!(PoeModule::set_claim_metadata(Origin::signed(1), claim.clone(), None));
+		assert_eq!(PoeModule::metadata(&claim), None);
+		assert_eq!(PoeModule::deposit(&claim), Some((1, 2)));
+		assert_eq!(Balances::reserved_balance(1), 2);
 	});
 }
 
+#[test]
+fn claim_lapses_unless_renewed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let (claim, renewed) = (vec![0, 1], vec![2, 3]);
+		assert_noop!(
+			PoeModule::create_claim_with_details(Origin::signed(1), claim.clone(), None, Some(1)),
+			Error::<Test>::ExpiryInPast
+		);
+		assert_ok!(PoeModule::create_claim_with_details(Origin::signed(1), claim.clone(), None, Some(3)));
+		assert_ok!(PoeModule::create_claim_with_details(Origin::signed(1), renewed.clone(), None, Some(3)));
+		assert_ok!(PoeModule::renew_claim(Origin::signed(1), renewed.clone(), Some(10)));
+		// A renewed claim is only listed under its new expiry.
+		assert_ok!(PoeModule::renew_claim(Origin::signed(1), renewed.clone(), Some(10)));
+		assert_eq!(PoeModule::claims_lapsing_at(4), vec![claim.clone()]);
+		assert_eq!(PoeModule::claims_lapsing_at(11), vec![renewed.clone()]);
+
+		System::set_block_number(4);
+		// Lapsed claims no longer exist, even before they are removed.
+		assert!(PoeModule::live_proof(&claim).is_none());
+		assert_noop!(PoeModule::renew_claim(Origin::signed(1), claim.clone(), None), Error::<Test>::ClaimNotExist);
+
+		<PoeModule as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Proofs::<Test>::get(&claim), None);
+		assert_eq!(PoeModule::expiry(&claim), None);
+		assert!(PoeModule::live_proof(&renewed).is_some());
+		assert_eq!(PoeModule::next_lapse_block(), 5);
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), claim.clone()));
+	});
+}
 
 #[test]
-fn creat_claim_failed_when_claim_is_not_exist() {
+fn lapsed_claim_can_be_claimed_before_cleanup() {
 	new_test_ext().execute_with(|| {
-		new_test_ext().execute_with(execute:||{
-			let claim:Vec<i32>=vec![0,1]；
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim_with_details(Origin::signed(1), claim.clone(), None, Some(2)));
 
-			assert_noop!(
-				PoeModule::create_claim(Origin::signed(1),claim.clone()),
-				Error::<Test>::ClaimNotExist
-			);
-		})
+		System::set_block_number(3);
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim), proof(vec![2], 1));
+		assert_eq!(PoeModule::expiry(&claim), None);
 	});
-}
\ No newline at end of file
+}
+
+#[test]
+fn claim_deposit_is_refunded_on_revoke_and_lapse() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let (claim, lapsing) = (vec![0; 30], vec![1; 10]);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone()));
+		assert_ok!(PoeModule::create_claim_with_details(Origin::signed(1), lapsing.clone(), None, Some(2)));
+		assert_eq!(Balances::reserved_balance(1), 40);
+		assert_eq!(PoeModule::deposit(&claim), Some((1, 30)));
+
+		// The deposit stays with the creator when the claim changes hands.
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2));
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(2), claim.clone()));
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(PoeModule::deposit(&claim), None);
+
+		System::set_block_number(3);
+		<PoeModule as OnInitialize<u64>>::on_initialize(3);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100);
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(3), vec![0; 40]));
+		assert_ok!(PoeModule::create_claim(Origin::signed(3), vec![1; 40]));
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(3), vec![2; 40]),
+			pallet_balances::Error::<Test, _>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn notarisation_fee_is_paid_to_receiver() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(PoeModule::set_notarisation_fee(Origin::signed(1), Some((3, 60))), sp_runtime::DispatchError::BadOrigin);
+		assert_ok!(PoeModule::set_notarisation_fee(Origin::root(), Some((3, 60))));
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![0, 1]));
+		assert_eq!(Balances::free_balance(1), 38);
+		assert_eq!(Balances::free_balance(3), 160);
+
+		// Without enough left for the fee nothing is claimed and nothing is reserved.
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(1), vec![0; 30]),
+			pallet_balances::Error::<Test, _>::InsufficientBalance
+		);
+
+		assert_ok!(PoeModule::set_notarisation_fee(Origin::root(), None));
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), vec![2, 3]));
+		assert_eq!(Balances::free_balance(3), 160);
+	});
+}
+
+#[test]
+fn batch_claim_proves_its_leaves() {
+	new_test_ext().execute_with(|| {
+		let leaves = [[1u8; 32], [2; 32], [3; 32]];
+		let hashes: Vec<_> = leaves.iter().map(merkle::leaf_hash).collect();
+		let left = merkle::node_hash(&hashes[0], &hashes[1]);
+		// The third leaf is moved up unchanged.
+		let root = merkle::node_hash(&left, &hashes[2]);
+
+		assert_noop!(PoeModule::create_batch_claim(Origin::signed(1), root, 0), Error::<Test>::EmptyBatch);
+		// A digest of the root does not stand in for the batch.
+		assert_noop!(
+			PoeModule::create_digest_claim(Origin::signed(2), HashAlgo::BatchRoot, root),
+			Error::<Test>::BatchRootClaim
+		);
+		assert_ok!(PoeModule::create_digest_claim(Origin::signed(2), HashAlgo::Blake2_256, root));
+		assert!(!PoeModule::verify_batch_leaf(root, leaves[0], 0, vec![hashes[1], hashes[2]]));
+		assert_ok!(PoeModule::create_batch_claim(Origin::signed(1), root, 3));
+
+		assert!(PoeModule::verify_batch_leaf(root, leaves[0], 0, vec![hashes[1], hashes[2]]));
+		assert!(PoeModule::verify_batch_leaf(root, leaves[1], 1, vec![hashes[0], hashes[2]]));
+		assert!(PoeModule::verify_batch_leaf(root, leaves[2], 2, vec![left]));
+		assert!(!PoeModule::verify_batch_leaf(root, leaves[2], 0, vec![left]));
+		assert!(!PoeModule::verify_batch_leaf(root, leaves[2], 2, vec![left, left]));
+		assert!(!PoeModule::verify_batch_leaf(root, leaves[0], 3, vec![]));
+
+		// Batches are owned like any other claim.
+		let claim = PoeModule::batch_claim(root);
+		assert_noop!(PoeModule::revoke_claim(Origin::signed(2), claim.clone()), Error::<Test>::NotClaimOwner);
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), claim.clone()));
+		assert_eq!(PoeModule::batch_leaf_count(&claim), None);
+		assert!(!PoeModule::verify_batch_leaf(root, leaves[2], 2, vec![left]));
+	});
+}
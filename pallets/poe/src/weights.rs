@@ -0,0 +1,144 @@
+//! Weights for pallet_poe
+//!
+//! PLACEHOLDER WEIGHTS, NOT MEASURED. The figures below are estimates in the layout of the benchmark
+//! CLI output and have not been produced by running the benchmarks in `benchmarking.rs`. The
+//! database reads and writes are counted from the code, the execution times are guesses. Replace
+//! this file by running on reference hardware:
+//!
+//! ./target/release/node-template benchmark --chain dev --execution=wasm --wasm-execution=compiled
+//!     --pallet pallet_poe --extrinsic '*' --steps 50 --repeat 20
+//!     --output ./pallets/poe/src/weights.rs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_poe.
+pub trait WeightInfo {
+	fn create_claim(l: u32, ) -> Weight;
+	fn create_digest_claim() -> Weight;
+	fn create_batch_claim() -> Weight;
+	fn create_shared_claim(l: u32, o: u32, ) -> Weight;
+	fn create_claim_with_details(l: u32, ) -> Weight;
+	fn set_claim_metadata(l: u32, ) -> Weight;
+	fn renew_claim(l: u32, ) -> Weight;
+	fn revoke_claim(l: u32, ) -> Weight;
+	fn transfer_claim(l: u32, ) -> Weight;
+}
+
+/// Placeholder weights for pallet_poe, see the module docs.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn create_claim(l: u32, ) -> Weight {
+		(98_610_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn create_digest_claim() -> Weight {
+		(99_870_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn create_batch_claim() -> Weight {
+		(101_530_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn create_shared_claim(l: u32, o: u32, ) -> Weight {
+		(101_240_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((412_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn create_claim_with_details(l: u32, ) -> Weight {
+		(124_180_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	fn set_claim_metadata(l: u32, ) -> Weight {
+		(71_460_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn renew_claim(l: u32, ) -> Weight {
+		(46_920_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn revoke_claim(l: u32, ) -> Weight {
+		(63_850_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	fn transfer_claim(l: u32, ) -> Weight {
+		(57_310_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn create_claim(l: u32, ) -> Weight {
+		(98_610_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn create_digest_claim() -> Weight {
+		(99_870_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn create_batch_claim() -> Weight {
+		(101_530_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn create_shared_claim(l: u32, o: u32, ) -> Weight {
+		(101_240_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((412_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn create_claim_with_details(l: u32, ) -> Weight {
+		(124_180_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn set_claim_metadata(l: u32, ) -> Weight {
+		(71_460_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn renew_claim(l: u32, ) -> Weight {
+		(46_920_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn revoke_claim(l: u32, ) -> Weight {
+		(63_850_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn transfer_claim(l: u32, ) -> Weight {
+		(57_310_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+}
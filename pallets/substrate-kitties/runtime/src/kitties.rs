@@ -1,90 +1,317 @@
 use support::{
     decl_storage, decl_module, decl_event, ensure, StorageValue, StorageMap, 
-    dispatch::Result, Parameter, traits::Currency
+    dispatch::Result, Parameter, traits::{Currency, ReservableCurrency}
 };
-use runtime_primitives::traits::{SimpleArithmetic, Bounded, One, Member};
-use parity_codec::{Encode, Decode, Input, Output};
+use runtime_primitives::{Permill, traits::{SimpleArithmetic, Bounded, One, Zero, Member, As, CheckedAdd, Saturating}};
+use parity_codec::{Codec, Encode, Decode, Input, Output};
 use runtime_io::blake2_128;
-use system::ensure_signed;
-use rstd::result;
+use system::{ensure_signed, ensure_root};
+use rstd::{result, prelude::*};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
 use crate::linked_item::{LinkedList, LinkedItem};
+use crate::genetics::{self, KittyTraits};
 
 pub trait Trait: system::Trait {
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
     type KittyIndex: Parameter + Member + Bounded + SimpleArithmetic + Default + Copy;
-    type Currency: Currency<Self::AccountId>;
+    type Currency: ReservableCurrency<Self::AccountId>;
 }
 
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
 
-// #[derive(Encode, Decode)]
-pub struct Kitty(pub [u8; 16]);
+/// Version of the module's storage, bumped whenever existing entries need a migration.
+const STORAGE_VERSION: u32 = 2;
 
+// #[derive(Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, PartialEq, Eq))]
+pub struct Kitty<KittyIndex, BlockNumber> {
+    pub dna: [u8; 16],
+    /// `None` for kitties that were created rather than bred.
+    pub parents: Option<(KittyIndex, KittyIndex)>,
+    pub generation: u16,
+    pub birth: BlockNumber,
+}
 
-impl Decode for Kitty
+impl<KittyIndex, BlockNumber> Decode for Kitty<KittyIndex, BlockNumber> where
+    KittyIndex: Decode,
+    BlockNumber: Decode + Default,
 {
     fn decode<I: Input>(input: &mut I) -> Option<Self> {
-        Some(Kitty(Decode::decode(input)?))
+        let dna = Decode::decode(input)?;
+        // Kitties stored before storage version 1 only hold their DNA.
+        let parents = match Decode::decode(input) {
+            Some(parents) => parents,
+            None => return Some(Kitty { dna, parents: None, generation: 0, birth: Default::default() }),
+        };
+        Some(Kitty {
+            dna,
+            parents,
+            generation: Decode::decode(input)?,
+            birth: Decode::decode(input)?,
+        })
     }
 }
 
-impl Encode for Kitty
+impl<KittyIndex, BlockNumber> Encode for Kitty<KittyIndex, BlockNumber> where
+    KittyIndex: Encode,
+    BlockNumber: Encode,
 {
     fn encode_to<T: Output>(&self, dest: &mut T) {
-        dest.write(&Encode::encode(&self.0));
+        dest.write(&Encode::encode(&self.dna));
+        dest.write(&Encode::encode(&self.parents));
+        dest.write(&Encode::encode(&self.generation));
+        dest.write(&Encode::encode(&self.birth));
+    }
+}
+
+impl<KittyIndex: PartialEq + Copy, BlockNumber> Kitty<KittyIndex, BlockNumber> {
+    fn is_parent(&self, kitty_id: KittyIndex) -> bool {
+        self.parents.map(|(a, b)| a == kitty_id || b == kitty_id).unwrap_or(false)
+    }
+
+    /// Whether breeding the two kitties would be inbreeding: one is a parent of the other or they share a parent.
+    fn is_related(&self, kitty_id: KittyIndex, other: &Self, other_id: KittyIndex) -> bool {
+        if self.is_parent(other_id) || other.is_parent(kitty_id) {
+            return true;
+        }
+        match (self.parents, other.parents) {
+            (Some((a, b)), Some(_)) => other.is_parent(a) || other.is_parent(b),
+            _ => false,
+        }
+    }
+}
+
+/// How the price of an auction is discovered.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq)]
+pub enum AuctionKind {
+    /// Ascending bids, the highest bid wins once the auction ends.
+    English,
+    /// The price falls from the start price to the reserve price, the first bid wins.
+    Dutch,
+}
+
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+pub struct Auction<AccountId, Balance, BlockNumber> {
+    pub seller: AccountId,
+    pub kind: AuctionKind,
+    /// Lowest accepted bid of an English auction, opening price of a Dutch auction.
+    pub start_price: Balance,
+    /// Lowest price the kitty will be sold for.
+    pub reserve_price: Balance,
+    pub start: BlockNumber,
+    pub end: BlockNumber,
+    /// Current winning bid, its funds are reserved on the bidder's account.
+    pub bid: Option<(AccountId, Balance)>,
+}
+
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+pub struct Offer<Balance, BlockNumber> {
+    /// Reserved on the account making the offer.
+    pub amount: Balance,
+    /// Last block the offer can be accepted in.
+    pub expiry: BlockNumber,
+}
+
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+pub struct SireListing<Balance, BlockNumber> {
+    /// Fee paid to the owner of the sire for every breeding.
+    pub price: Balance,
+    /// Last block the sire can be bred with.
+    pub expiry: BlockNumber,
+}
+
+/// Maximum number of open offers on a single kitty.
+const MAX_OFFERS_PER_KITTY: usize = 16;
+
+/// Why a kitty can not be minted.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum MintError {
+    /// All kitty ids are taken.
+    KittiesCountOverflow,
+    /// The account owns `MaxKittiesPerAccount` kitties.
+    TooManyKitties,
+    /// The account can not reserve the creation deposit.
+    InsufficientDeposit,
+}
+
+impl From<MintError> for &'static str {
+    fn from(err: MintError) -> &'static str {
+        match err {
+            MintError::KittiesCountOverflow => "Kitties count overflow",
+            MintError::TooManyKitties => "Too many kitties",
+            MintError::InsufficientDeposit => "Can not reserve creation deposit",
+        }
     }
 }
 
+/// Maximum length in bytes of the memo of a gift.
+pub const MAX_MEMO_LEN: usize = 140;
+
+/// Maximum number of kitties returned by a single page of the runtime API.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Maximum number of kitties migrated in a block.
+pub const MIGRATION_BATCH_SIZE: u32 = 100;
+
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+pub struct KittyInfo<AccountId, KittyIndex, Balance> {
+    pub id: KittyIndex,
+    pub dna: [u8; 16],
+    /// `None` if the kitty is not for sale.
+    pub price: Option<Balance>,
+    pub owner: AccountId,
+}
+
+/// A page of the kitties owned by an account, in the order of its `OwnedKitties` list.
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+pub struct KittiesPage<AccountId, KittyIndex, Balance> {
+    pub kitties: Vec<KittyInfo<AccountId, KittyIndex, Balance>>,
+    /// Cursor of the next page, `None` if this is the last one.
+    pub next: Option<KittyIndex>,
+    /// Number of kitties owned by the account.
+    pub total: u64,
+}
+
 // #[cfg_attr(feature = "std", derive(Debug, PartialEq, Eq))]
 // #[derive(Encode, Decode)]
 // pub struct KittyLinkedItem<T: Trait> {
 //     pub prev: Option<T::KittyIndex>,
 //     pub next: Option<T::KittyIndex>,
 // }
+type KittyOf<T> = Kitty<<T as Trait>::KittyIndex, <T as system::Trait>::BlockNumber>;
 type KittyLinkedItem<T> = LinkedItem<<T as Trait>::KittyIndex>;
-type OwnedKittiesList<T> = LinkedList<OwnedKitties<T>, <T as system::Trait>::AccountId, <T as Trait>::KittyIndex>;
+type OwnedKittiesList<T> = LinkedList<OwnedKitties<T>, OwnedKittiesCount<T>, <T as system::Trait>::AccountId, <T as Trait>::KittyIndex>;
 
 decl_storage! {
     trait Store for Module<T: Trait> as Kitties {
-        pub Kitties get(kitty): map T::KittyIndex => Option<Kitty>;
+        pub Kitties get(kitty): map T::KittyIndex => Option<KittyOf<T>>;
         pub KittiesCount get(kitties_count): T::KittyIndex;
         // pub OwnedKitties get(owned_kitties): map (T::AccountId, T::KittyIndex) => T::KittyIndex;
-        // pub OwnedKittiesCount get(owned_kitties_count): map T::AccountId => T::KittyIndex;
         pub OwnedKitties get(owned_kitties): map (T::AccountId, Option<T::KittyIndex>) => Option<KittyLinkedItem<T>>;
+        pub OwnedKittiesCount get(owned_kitties_count): map T::AccountId => u32;
 
         pub KittyOwners get(kitty_owner): map T::KittyIndex => Option<T::AccountId>;
 
         pub KittyPrices get(kitty_price): map T::KittyIndex => Option<BalanceOf<T>>;
+
+        // Reserved from the minting account for every new kitty, returned when the kitty is burned.
+        pub MintDeposit get(mint_deposit) config(): BalanceOf<T>;
+        // Most kitties an account can mint up to, 0 for no limit.
+        pub MaxKittiesPerAccount get(max_kitties_per_account) config(): u32;
+        pub KittyDeposits get(kitty_deposit): map T::KittyIndex => Option<(T::AccountId, BalanceOf<T>)>;
+
+        pub Auctions get(auction): map T::KittyIndex => Option<Auction<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+        // Auctions to settle at the end of a block. Entries can be stale, the auction is checked again on settlement.
+        pub AuctionsEndingAt get(auctions_ending_at): map T::BlockNumber => Vec<T::KittyIndex>;
+
+        pub Offers get(offer): map (T::KittyIndex, T::AccountId) => Option<Offer<BalanceOf<T>, T::BlockNumber>>;
+        pub KittyOffers get(kitty_offers): map T::KittyIndex => Vec<T::AccountId>;
+        // Offers to sweep at the end of a block, one entry per pending offer.
+        pub OffersExpiringAt get(offers_expiring_at): map T::BlockNumber => Vec<(T::KittyIndex, T::AccountId)>;
+
+        // Kitties that can be bred with by anyone paying the fee to their owner.
+        pub SireListings get(sire_listing): map T::KittyIndex => Option<SireListing<BalanceOf<T>, T::BlockNumber>>;
+
+        // Chance of an allele of a newborn kitty to mutate.
+        pub MutationRate get(mutation_rate) config(): Permill;
+
+        // Cooldown of a generation 0 parent, a parent of generation n waits n + 1 times as long.
+        pub BreedingCooldown get(breeding_cooldown) config(): T::BlockNumber;
+        // First block a kitty can breed again.
+        pub KittyCooldowns get(kitty_cooldown): map T::KittyIndex => T::BlockNumber;
+
+        pub StorageVersion get(storage_version): u32;
+        // Next kitty to migrate and the number of kitties when the migration started, while it runs.
+        pub MigrationCursor get(migration_cursor): Option<(T::KittyIndex, T::KittyIndex)>;
     }
 }
 
 decl_event! (
     pub enum Event<T> where
         <T as system::Trait>::AccountId,
+        <T as system::Trait>::BlockNumber,
         <T as Trait>::KittyIndex,
         Balance = BalanceOf<T>,
-    {   
+    {
         // A kitty is created. (owner, kitty_id)
         Created(AccountId, KittyIndex),
         // A kitty is available for sale. (from, to, kitty_id)
         Transferred(AccountId, AccountId, KittyIndex),
+        /// A kitty is given away with a memo. (from, to, kitty_id, memo)
+        Gifted(AccountId, AccountId, KittyIndex, Vec<u8>),
+        /// A kitty is burned. (owner, kitty_id)
+        Burned(AccountId, KittyIndex),
         /// A kitty is available for sale. (owner, kitty_id, price)
         Ask(AccountId, KittyIndex, Option<Balance>),
         // A kitty is sold. (from, to, kitty_id, price)
         Sold(AccountId, AccountId, KittyIndex, Balance),
-
+        /// An auction is opened. (seller, kitty_id, kind, end)
+        AuctionCreated(AccountId, KittyIndex, AuctionKind, BlockNumber),
+        /// A bid is placed. (bidder, kitty_id, amount)
+        Bid(AccountId, KittyIndex, Balance),
+        /// An auction without bids is cancelled. (seller, kitty_id)
+        AuctionCancelled(AccountId, KittyIndex),
+        /// An auction is settled. (seller, buyer, kitty_id, price)
+        AuctionSettled(AccountId, AccountId, KittyIndex, Balance),
+        /// An auction ended without a bid meeting the reserve price. (seller, kitty_id)
+        AuctionFailed(AccountId, KittyIndex),
+        /// An offer is made. (buyer, kitty_id, amount, expiry)
+        OfferMade(AccountId, KittyIndex, Balance, BlockNumber),
+        /// An offer is withdrawn. (buyer, kitty_id)
+        OfferWithdrawn(AccountId, KittyIndex),
+        /// An offer is accepted. (from, to, kitty_id, amount)
+        OfferAccepted(AccountId, AccountId, KittyIndex, Balance),
+        /// An offer expired and its funds are released. (buyer, kitty_id)
+        OfferExpired(AccountId, KittyIndex),
+        /// A kitty is available as a sire. (owner, kitty_id, price, expiry)
+        SireListed(AccountId, KittyIndex, Balance, BlockNumber),
+        /// A sire listing is cancelled. (owner, kitty_id)
+        SireCancelled(AccountId, KittyIndex),
+        /// A listed sire is bred with. (breeder, sire owner, sire_id, price)
+        Sired(AccountId, AccountId, KittyIndex, Balance),
     }
 );
 
+client::decl_runtime_apis! {
+    pub trait KittiesApi<AccountId, KittyIndex, Balance> where
+        AccountId: Codec,
+        KittyIndex: Codec,
+        Balance: Codec,
+    {
+        /// The decoded traits of a kitty, `None` if it does not exist.
+        fn kitty_traits(kitty_id: KittyIndex) -> Option<KittyTraits>;
+        /// Number of kitties ever created.
+        fn kitties_count() -> KittyIndex;
+        /// Number of kitties owned by `account`.
+        fn owned_kitties_count(account: AccountId) -> u64;
+        /// Up to `limit` kitties of `account` starting at `cursor`, from the first kitty if `cursor` is `None`.
+        fn owned_kitties(account: AccountId, cursor: Option<KittyIndex>, limit: u32) -> KittiesPage<AccountId, KittyIndex, Balance>;
+    }
+}
+
 decl_module! {
     pub struct Module<T: Trait> for enum Call where origin: T::Origin {
         fn deposit_event<T>() = default;
 
         pub fn create(origin) {
             let sender = ensure_signed(origin)?;
-            let kitty_id = Self::next_kitty_id()?;
+            let kitty_id = Self::ensure_can_mint(&sender)?;
             let dna = Self::random_value(&sender);
-            let kitty = Kitty(dna);
+            let kitty = Kitty {
+                dna,
+                parents: None,
+                generation: 0,
+                birth: <system::Module<T>>::block_number(),
+            };
+            Self::reserve_mint_deposit(&sender, kitty_id)?;
             Self::insert_kitty(&sender, kitty_id, kitty);
 
             Self::deposit_event(RawEvent::Created(sender, kitty_id));
@@ -100,16 +327,40 @@ decl_module! {
         pub fn transfer(origin, to: T::AccountId, kitty_id: T::KittyIndex) {
             let sender = ensure_signed(origin)?;
             ensure!(<OwnedKitties<T>>::exists(&(sender.clone(), Some(kitty_id))), "Only owner can transfer kitty");
+            ensure!(!<Auctions<T>>::exists(kitty_id), "Kitty is in auction");
             Self::do_transfer(&sender, &to, kitty_id);
 
             Self::deposit_event(RawEvent::Transferred(sender, to, kitty_id));
         }
 
+        pub fn gift(origin, to: T::AccountId, kitty_id: T::KittyIndex, memo: Vec<u8>) {
+            let sender = ensure_signed(origin)?;
+            ensure!(memo.len() <= MAX_MEMO_LEN, "Memo is too long");
+            ensure!(<OwnedKitties<T>>::exists(&(sender.clone(), Some(kitty_id))), "Only owner can gift kitty");
+            ensure!(!<Auctions<T>>::exists(kitty_id), "Kitty is in auction");
+
+            <KittyPrices<T>>::remove(kitty_id);
+            Self::do_transfer(&sender, &to, kitty_id);
+
+            Self::deposit_event(RawEvent::Gifted(sender, to, kitty_id, memo));
+        }
+
+        /// Releases a kitty to the wild, removing it for good. Open offers on it and its creation deposit are refunded.
+        pub fn burn(origin, kitty_id: T::KittyIndex) {
+            let sender = ensure_signed(origin)?;
+            ensure!(<OwnedKitties<T>>::exists(&(sender.clone(), Some(kitty_id))), "Only owner can burn kitty");
+            ensure!(!<Auctions<T>>::exists(kitty_id), "Kitty is in auction");
+
+            Self::do_burn(&sender, kitty_id);
+
+            Self::deposit_event(RawEvent::Burned(sender, kitty_id));
+        }
 
         pub fn ask(origin, kitty_id: T::KittyIndex, price: Option<BalanceOf<T>>) {
             let sender = ensure_signed(origin)?;
 
             ensure!(<OwnedKitties<T>>::exists(&(sender.clone(), Some(kitty_id))), "Only owner can set price for kitty");
+            ensure!(!<Auctions<T>>::exists(kitty_id), "Kitty is in auction");
 
             if let Some(price) = price {
                 <KittyPrices<T>>::insert(kitty_id, price);
@@ -142,11 +393,237 @@ decl_module! {
             Self::deposit_event(RawEvent::Sold(owner, sender, kitty_id, kitty_price));
 
         }
-    }
-}
 
-fn combine_dna(dna1: u8, dna2: u8, selector: u8) -> u8 {
-    ((selector & dna1) | (!selector & dna2))
+        pub fn create_auction(origin, kitty_id: T::KittyIndex, kind: AuctionKind, start_price: BalanceOf<T>, reserve_price: BalanceOf<T>, end: T::BlockNumber) {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(<OwnedKitties<T>>::exists(&(sender.clone(), Some(kitty_id))), "Only owner can auction kitty");
+            ensure!(!<Auctions<T>>::exists(kitty_id), "Kitty is already in auction");
+
+            let now = <system::Module<T>>::block_number();
+            ensure!(end > now, "Auction must end in the future");
+            if kind == AuctionKind::Dutch {
+                ensure!(start_price >= reserve_price, "Start price is below reserve price");
+            }
+
+            // An auctioned kitty can not be bought at a fixed price at the same time.
+            <KittyPrices<T>>::remove(kitty_id);
+
+            <Auctions<T>>::insert(kitty_id, Auction {
+                seller: sender.clone(),
+                kind,
+                start_price,
+                reserve_price,
+                start: now,
+                end,
+                bid: None,
+            });
+            <AuctionsEndingAt<T>>::mutate(end, |kitties| kitties.push(kitty_id));
+
+            Self::deposit_event(RawEvent::AuctionCreated(sender, kitty_id, kind, end));
+        }
+
+        pub fn bid(origin, kitty_id: T::KittyIndex, amount: BalanceOf<T>) {
+            let sender = ensure_signed(origin)?;
+
+            let mut auction = Self::auction(kitty_id).ok_or("Auction does not exist")?;
+            ensure!(auction.seller != sender, "Seller can not bid");
+
+            let now = <system::Module<T>>::block_number();
+            ensure!(now < auction.end, "Auction has ended");
+
+            let amount = match auction.kind {
+                AuctionKind::English => {
+                    ensure!(amount >= auction.start_price, "Bid is below start price");
+                    if let Some((_, ref highest)) = auction.bid {
+                        ensure!(amount > *highest, "Bid is too low");
+                    }
+
+                    T::Currency::reserve(&sender, amount)?;
+                    if let Some((bidder, highest)) = auction.bid.take() {
+                        T::Currency::unreserve(&bidder, highest);
+                    }
+                    amount
+                },
+                AuctionKind::Dutch => {
+                    let price = Self::dutch_price(&auction, now);
+                    ensure!(amount >= price, "Bid is below current price");
+
+                    T::Currency::reserve(&sender, price)?;
+                    // The first bid wins, settle at the end of this block.
+                    auction.end = now;
+                    <AuctionsEndingAt<T>>::mutate(now, |kitties| kitties.push(kitty_id));
+                    price
+                },
+            };
+
+            auction.bid = Some((sender.clone(), amount));
+            <Auctions<T>>::insert(kitty_id, auction);
+
+            Self::deposit_event(RawEvent::Bid(sender, kitty_id, amount));
+        }
+
+        pub fn cancel_auction(origin, kitty_id: T::KittyIndex) {
+            let sender = ensure_signed(origin)?;
+
+            let auction = Self::auction(kitty_id).ok_or("Auction does not exist")?;
+            ensure!(auction.seller == sender, "Only seller can cancel auction");
+            ensure!(auction.bid.is_none(), "Auction already has a bid");
+
+            <Auctions<T>>::remove(kitty_id);
+
+            Self::deposit_event(RawEvent::AuctionCancelled(sender, kitty_id));
+        }
+
+        pub fn make_offer(origin, kitty_id: T::KittyIndex, amount: BalanceOf<T>, expiry: T::BlockNumber) {
+            let sender = ensure_signed(origin)?;
+
+            let owner = Self::kitty_owner(kitty_id).ok_or("Kitty does not exist")?;
+            ensure!(owner != sender, "Can not make offer for own kitty");
+            ensure!(expiry >= <system::Module<T>>::block_number(), "Offer already expired");
+
+            let previous = Self::offer((kitty_id, sender.clone()));
+            if previous.is_none() {
+                ensure!(Self::kitty_offers(kitty_id).len() < MAX_OFFERS_PER_KITTY, "Too many offers for kitty");
+            }
+
+            T::Currency::reserve(&sender, amount)?;
+            let scheduled = match previous {
+                Some(previous) => {
+                    T::Currency::unreserve(&sender, previous.amount);
+                    if previous.expiry != expiry {
+                        Self::unschedule_offer(previous.expiry, kitty_id, &sender);
+                    }
+                    previous.expiry == expiry
+                },
+                None => {
+                    <KittyOffers<T>>::mutate(kitty_id, |offers| offers.push(sender.clone()));
+                    false
+                },
+            };
+
+            <Offers<T>>::insert((kitty_id, sender.clone()), Offer { amount, expiry });
+            if !scheduled {
+                <OffersExpiringAt<T>>::mutate(expiry, |offers| offers.push((kitty_id, sender.clone())));
+            }
+
+            Self::deposit_event(RawEvent::OfferMade(sender, kitty_id, amount, expiry));
+        }
+
+        pub fn withdraw_offer(origin, kitty_id: T::KittyIndex) {
+            let sender = ensure_signed(origin)?;
+
+            let offer = Self::remove_offer(kitty_id, &sender).ok_or("Offer does not exist")?;
+            T::Currency::unreserve(&sender, offer.amount);
+
+            Self::deposit_event(RawEvent::OfferWithdrawn(sender, kitty_id));
+        }
+
+        pub fn accept_offer(origin, kitty_id: T::KittyIndex, buyer: T::AccountId) {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(<OwnedKitties<T>>::exists(&(sender.clone(), Some(kitty_id))), "Only owner can accept offer");
+            ensure!(!<Auctions<T>>::exists(kitty_id), "Kitty is in auction");
+
+            let offer = Self::offer((kitty_id, buyer.clone())).ok_or("Offer does not exist")?;
+            ensure!(offer.expiry >= <system::Module<T>>::block_number(), "Offer expired");
+
+            // Everything that can fail is checked, move the funds first and then the kitty.
+            T::Currency::repatriate_reserved(&buyer, &sender, offer.amount)?;
+            Self::remove_offer(kitty_id, &buyer);
+            <KittyPrices<T>>::remove(kitty_id);
+            Self::do_transfer(&sender, &buyer, kitty_id);
+
+            Self::deposit_event(RawEvent::OfferAccepted(sender, buyer, kitty_id, offer.amount));
+        }
+
+        pub fn list_sire(origin, kitty_id: T::KittyIndex, price: BalanceOf<T>, expiry: T::BlockNumber) {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(<OwnedKitties<T>>::exists(&(sender.clone(), Some(kitty_id))), "Only owner can list kitty as sire");
+            ensure!(expiry >= <system::Module<T>>::block_number(), "Sire listing already expired");
+
+            <SireListings<T>>::insert(kitty_id, SireListing { price, expiry });
+
+            Self::deposit_event(RawEvent::SireListed(sender, kitty_id, price, expiry));
+        }
+
+        pub fn cancel_sire(origin, kitty_id: T::KittyIndex) {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(<OwnedKitties<T>>::exists(&(sender.clone(), Some(kitty_id))), "Only owner can cancel sire listing");
+            ensure!(<SireListings<T>>::exists(kitty_id), "Kitty is not listed as sire");
+
+            <SireListings<T>>::remove(kitty_id);
+
+            Self::deposit_event(RawEvent::SireCancelled(sender, kitty_id));
+        }
+
+        /// Breeds `kitty_id` with a listed sire, paying its fee to the owner of the sire. Fails if the
+        /// fee was raised above `max_fee` after the call was signed.
+        pub fn breed_with_sire(origin, kitty_id: T::KittyIndex, sire_id: T::KittyIndex, max_fee: BalanceOf<T>) {
+            let sender = ensure_signed(origin)?;
+
+            let listing = Self::sire_listing(sire_id).ok_or("Kitty is not listed as sire")?;
+            ensure!(listing.expiry >= <system::Module<T>>::block_number(), "Sire listing expired");
+            ensure!(listing.price <= max_fee, "Sire fee above maximum");
+
+            let sire_owner = Self::kitty_owner(sire_id).ok_or("Invalid kitty_id_2")?;
+            ensure!(sire_owner != sender, "Can not pay for own sire");
+
+            // Everything that can fail is done before the fee is paid. A failed fee payment returns
+            // the creation deposit, nothing else has changed by then.
+            let (kitty, sire) = Self::check_breed(&sender, &sire_owner, kitty_id, sire_id)?;
+            let cost = listing.price.checked_add(&Self::mint_deposit()).ok_or("Sire fee and creation deposit overflow")?;
+            ensure!(T::Currency::free_balance(&sender) >= cost, "Can not pay sire fee and creation deposit");
+            let new_kitty_id = Self::next_kitty_id()?;
+            Self::reserve_mint_deposit(&sender, new_kitty_id)?;
+            if let Err(err) = T::Currency::transfer(&sender, &sire_owner, listing.price) {
+                Self::release_mint_deposit(new_kitty_id);
+                return Err(err);
+            }
+            Self::breed_child(&sender, new_kitty_id, kitty_id, kitty, sire_id, sire);
+
+            Self::deposit_event(RawEvent::Sired(sender.clone(), sire_owner, sire_id, listing.price));
+            Self::deposit_event(RawEvent::Created(sender, new_kitty_id));
+        }
+
+        pub fn set_mutation_rate(origin, rate: Permill) {
+            ensure_root(origin)?;
+            <MutationRate<T>>::put(rate);
+        }
+
+        pub fn set_breeding_cooldown(origin, cooldown: T::BlockNumber) {
+            ensure_root(origin)?;
+            <BreedingCooldown<T>>::put(cooldown);
+        }
+
+        /// Changes the deposit of new kitties, existing kitties keep the deposit they were minted with.
+        pub fn set_mint_deposit(origin, deposit: BalanceOf<T>) {
+            ensure_root(origin)?;
+            <MintDeposit<T>>::put(deposit);
+        }
+
+        pub fn set_max_kitties_per_account(origin, max: u32) {
+            ensure_root(origin)?;
+            <MaxKittiesPerAccount<T>>::put(max);
+        }
+
+        fn on_initialize(_n: T::BlockNumber) {
+            if Self::storage_version() < STORAGE_VERSION {
+                Self::migrate_kitties();
+            }
+        }
+
+        fn on_finalize(n: T::BlockNumber) {
+            for kitty_id in <AuctionsEndingAt<T>>::take(n) {
+                Self::settle_auction(kitty_id, n);
+            }
+            for (kitty_id, buyer) in <OffersExpiringAt<T>>::take(n) {
+                Self::expire_offer(kitty_id, buyer, n);
+            }
+        }
+    }
 }
 
 // impl<T: Trait> OwnedKitties<T> {
@@ -225,10 +702,23 @@ impl<T: Trait> Module<T> {
         payload.using_encoded(blake2_128)
     }
 
-    fn next_kitty_id() -> result::Result<T::KittyIndex, &'static str> {
+    fn next_kitty_id() -> result::Result<T::KittyIndex, MintError> {
         let kitty_id = Self::kitties_count();
         if kitty_id == T::KittyIndex::max_value(){
-            return Err("Kitties count overflow");
+            return Err(MintError::KittiesCountOverflow);
+        }
+        Ok(kitty_id)
+    }
+
+    /// The id of the next kitty if `owner` can mint it. The per-account limit only applies to minting, transfers are not capped.
+    fn ensure_can_mint(owner: &T::AccountId) -> result::Result<T::KittyIndex, MintError> {
+        let kitty_id = Self::next_kitty_id()?;
+        let max = Self::max_kitties_per_account();
+        if max != 0 && Self::owned_kitties_count(owner) >= max {
+            return Err(MintError::TooManyKitties);
+        }
+        if !T::Currency::can_reserve(owner, Self::mint_deposit()) {
+            return Err(MintError::InsufficientDeposit);
         }
         Ok(kitty_id)
     }
@@ -238,7 +728,23 @@ impl<T: Trait> Module<T> {
         <OwnedKittiesList<T>>::append(owner, kitty_id);
     }
 
-    fn insert_kitty(owner: &T::AccountId, kitty_id: T::KittyIndex, kitty: Kitty) {
+    /// Reserves the creation deposit of `kitty_id` from its minter, before the kitty is inserted.
+    fn reserve_mint_deposit(owner: &T::AccountId, kitty_id: T::KittyIndex) -> result::Result<(), MintError> {
+        let deposit = Self::mint_deposit();
+        T::Currency::reserve(owner, deposit).map_err(|_| MintError::InsufficientDeposit)?;
+        if !deposit.is_zero() {
+            <KittyDeposits<T>>::insert(kitty_id, (owner.clone(), deposit));
+        }
+        Ok(())
+    }
+
+    fn release_mint_deposit(kitty_id: T::KittyIndex) {
+        if let Some((depositor, deposit)) = <KittyDeposits<T>>::take(kitty_id) {
+            T::Currency::unreserve(&depositor, deposit);
+        }
+    }
+
+    fn insert_kitty(owner: &T::AccountId, kitty_id: T::KittyIndex, kitty: KittyOf<T>) {
         <Kitties<T>>::insert(kitty_id, kitty);
         <KittiesCount<T>>::put(kitty_id + One::one());
         <KittyOwners<T>>::insert(kitty_id, owner);
@@ -252,12 +758,57 @@ impl<T: Trait> Module<T> {
     fn do_transfer(from: &T::AccountId, to: &T::AccountId, kitty_id: T::KittyIndex) {
         // <OwnedKitties<T>>::remove(&from, kitty_id);
         // <OwnedKitties<T>>::append(&to, kitty_id);
+        let uncounted = Self::is_uncounted(kitty_id);
+        if uncounted {
+            <OwnedKittiesCount<T>>::mutate(from, |count| *count += 1);
+        }
         <OwnedKittiesList<T>>::remove(&from, kitty_id);
 		<OwnedKittiesList<T>>::append(&to, kitty_id);
+        if uncounted {
+            <OwnedKittiesCount<T>>::mutate(to, |count| *count -= 1);
+        }
         <KittyOwners<T>>::insert(kitty_id, to);
+        // A sire listing is an offer of the previous owner.
+        <SireListings<T>>::remove(kitty_id);
+    }
+
+    /// Removes a kitty and everything attached to it. Kitty ids are never reused, so children of a burned kitty keep its id as parent.
+    fn do_burn(owner: &T::AccountId, kitty_id: T::KittyIndex) {
+        for buyer in <KittyOffers<T>>::take(kitty_id) {
+            if let Some(offer) = <Offers<T>>::take((kitty_id, buyer.clone())) {
+                T::Currency::unreserve(&buyer, offer.amount);
+                Self::unschedule_offer(offer.expiry, kitty_id, &buyer);
+            }
+        }
+
+        if Self::is_uncounted(kitty_id) {
+            <OwnedKittiesCount<T>>::mutate(owner, |count| *count += 1);
+        }
+        <OwnedKittiesList<T>>::remove(owner, kitty_id);
+        <Kitties<T>>::remove(kitty_id);
+        <KittyOwners<T>>::remove(kitty_id);
+        <KittyPrices<T>>::remove(kitty_id);
+        <SireListings<T>>::remove(kitty_id);
+        <KittyCooldowns<T>>::remove(kitty_id);
+
+        Self::release_mint_deposit(kitty_id);
     }
 
     fn do_breed(sender: &T::AccountId, kitty_id_1: T::KittyIndex, kitty_id_2: T::KittyIndex) -> result::Result<T::KittyIndex, &'static str> {
+        let (kitty1, kitty2) = Self::check_breed(sender, sender, kitty_id_1, kitty_id_2)?;
+        let kitty_id = Self::next_kitty_id()?;
+        Self::reserve_mint_deposit(sender, kitty_id)?;
+        Self::breed_child(sender, kitty_id, kitty_id_1, kitty1, kitty_id_2, kitty2);
+        Ok(kitty_id)
+    }
+
+    /// Checks that `sender` can breed its kitty `kitty_id_1` with `kitty_id_2` owned by `sire_owner`.
+    fn check_breed(
+        sender: &T::AccountId,
+        sire_owner: &T::AccountId,
+        kitty_id_1: T::KittyIndex,
+        kitty_id_2: T::KittyIndex,
+    ) -> result::Result<(KittyOf<T>, KittyOf<T>), &'static str> {
         let kitty1 = Self::kitty(kitty_id_1);
         let kitty2 = Self::kitty(kitty_id_2);
 
@@ -265,23 +816,192 @@ impl<T: Trait> Module<T> {
         ensure!(kitty2.is_some(), "Invalid kitty_id_2");
         ensure!(kitty_id_1 != kitty_id_2, "Needs different parent");
         ensure!(Self::kitty_owner(&kitty_id_1).map(|owner| owner == *sender).unwrap_or(false), "Not onwer of kitty1");
-        ensure!(Self::kitty_owner(&kitty_id_2).map(|owner| owner == *sender).unwrap_or(false), "Not onwer of kitty2");
+        ensure!(Self::kitty_owner(&kitty_id_2).map(|owner| owner == *sire_owner).unwrap_or(false), "Not onwer of kitty2");
 
-        let kitty_id = Self::next_kitty_id()?;
+        let kitty1 = kitty1.unwrap();
+        let kitty2 = kitty2.unwrap();
+
+        let now = <system::Module<T>>::block_number();
+        ensure!(now >= Self::kitty_cooldown(kitty_id_1), "Kitty1 is on breeding cooldown");
+        ensure!(now >= Self::kitty_cooldown(kitty_id_2), "Kitty2 is on breeding cooldown");
+        ensure!(!kitty1.is_related(kitty_id_1, &kitty2, kitty_id_2), "Parents are related");
+        Self::ensure_can_mint(sender)?;
 
-        let kitty1_dna = kitty1.unwrap().0;
-        let kitty2_dna = kitty2.unwrap().0;
+        Ok((kitty1, kitty2))
+    }
+
+    /// Inserts the child of two checked parents as `kitty_id`, its creation deposit is reserved already.
+    fn breed_child(
+        sender: &T::AccountId,
+        kitty_id: T::KittyIndex,
+        kitty_id_1: T::KittyIndex,
+        kitty1: KittyOf<T>,
+        kitty_id_2: T::KittyIndex,
+        kitty2: KittyOf<T>,
+    ) {
+        let now = <system::Module<T>>::block_number();
 
         let selector = Self::random_value(&sender);
+        let mutation = blake2_128(&selector);
+
+        let new_dna = genetics::inherit(&kitty1.dna, &kitty2.dna, &selector, &mutation, Self::mutation_rate());
+
+        Self::insert_kitty(sender, kitty_id, Kitty {
+            dna: new_dna,
+            parents: Some((kitty_id_1, kitty_id_2)),
+            generation: kitty1.generation.max(kitty2.generation).saturating_add(1),
+            birth: now,
+        });
+
+        <KittyCooldowns<T>>::insert(kitty_id_1, now + Self::cooldown_of(&kitty1));
+        <KittyCooldowns<T>>::insert(kitty_id_2, now + Self::cooldown_of(&kitty2));
+    }
+
+    fn cooldown_of(kitty: &KittyOf<T>) -> T::BlockNumber {
+        Self::breeding_cooldown() * <T::BlockNumber as As<u64>>::sa(u64::from(kitty.generation) + 1)
+    }
+
+    /// Migrates the next `MIGRATION_BATCH_SIZE` kitties to the current storage version, and sets the
+    /// version once every kitty that existed when the migration started is migrated.
+    ///
+    /// Kitties stored before lineage tracking are rewritten as generation 0 without parents. Kitties
+    /// were not counted in the length of the `OwnedKitties` lists before storage version 2, each one
+    /// is counted for the account owning it when the migration gets to it.
+    fn migrate_kitties() {
+        let version = Self::storage_version();
+        let (mut kitty_id, end) = Self::migration_cursor().unwrap_or_else(|| (Zero::zero(), Self::kitties_count()));
+        let mut batch = 0;
+        while kitty_id < end && batch < MIGRATION_BATCH_SIZE {
+            if version < 1 {
+                if let Some(kitty) = Self::kitty(kitty_id) {
+                    <Kitties<T>>::insert(kitty_id, kitty);
+                }
+            }
+            if version < 2 {
+                if let Some(owner) = Self::kitty_owner(kitty_id) {
+                    <OwnedKittiesCount<T>>::mutate(owner, |count| *count += 1);
+                }
+            }
+            kitty_id = kitty_id + One::one();
+            batch += 1;
+        }
+
+        if kitty_id < end {
+            <MigrationCursor<T>>::put((kitty_id, end));
+        } else {
+            <MigrationCursor<T>>::kill();
+            <StorageVersion<T>>::put(STORAGE_VERSION);
+        }
+    }
+
+    /// Whether the running migration has not counted `kitty_id` for its owner yet. Moving such a
+    /// kitty must leave the lengths of the `OwnedKitties` lists as they are.
+    fn is_uncounted(kitty_id: T::KittyIndex) -> bool {
+        Self::storage_version() < 2 && Self::migration_cursor().map_or(false, |(next, end)| kitty_id >= next && kitty_id < end)
+    }
+
+    fn kitty_info(owner: &T::AccountId, kitty_id: T::KittyIndex) -> Option<KittyInfo<T::AccountId, T::KittyIndex, BalanceOf<T>>> {
+        Self::kitty(kitty_id).map(|kitty| KittyInfo {
+            id: kitty_id,
+            dna: kitty.dna,
+            price: Self::kitty_price(kitty_id),
+            owner: owner.clone(),
+        })
+    }
+
+    /// A page of at most `limit` kitties of `account`, capped by `MAX_PAGE_SIZE`. An unknown cursor yields an empty page.
+    pub fn owned_kitties_page(
+        account: &T::AccountId,
+        cursor: Option<T::KittyIndex>,
+        limit: u32,
+    ) -> KittiesPage<T::AccountId, T::KittyIndex, BalanceOf<T>> {
+        let page_size = limit.min(MAX_PAGE_SIZE) as usize;
+        let iter = match cursor {
+            Some(kitty_id) => <OwnedKittiesList<T>>::iter_from(account, kitty_id),
+            None => <OwnedKittiesList<T>>::iter(account),
+        };
+        // One more than the page size, the extra kitty is the cursor of the next page.
+        let mut ids: Vec<_> = iter.take(page_size + 1).collect();
+        let next = if ids.len() > page_size { ids.pop() } else { None };
+
+        KittiesPage {
+            kitties: ids.into_iter().filter_map(|kitty_id| Self::kitty_info(account, kitty_id)).collect(),
+            next,
+            total: u64::from(Self::owned_kitties_count(account)),
+        }
+    }
+
+    pub fn kitty_traits(kitty_id: T::KittyIndex) -> Option<KittyTraits> {
+        Self::kitty(kitty_id).map(|kitty| genetics::decode(&kitty.dna))
+    }
 
-        let mut new_dna = [0u8; 16];
-        for i in 0..kitty1_dna.len() {
-            new_dna[i] = combine_dna(kitty1_dna[i], kitty2_dna[i], selector[i]);
+    /// The price of a Dutch auction falls linearly from the start price to the reserve price.
+    fn dutch_price(auction: &Auction<T::AccountId, BalanceOf<T>, T::BlockNumber>, now: T::BlockNumber) -> BalanceOf<T> {
+        if now >= auction.end {
+            return auction.reserve_price;
         }
-        
-        Self::insert_kitty(sender, kitty_id, Kitty(new_dna));
+        let elapsed = <BalanceOf<T> as As<u64>>::sa((now - auction.start).as_());
+        let duration = <BalanceOf<T> as As<u64>>::sa((auction.end - auction.start).as_());
+        // Split so that no product exceeds the price range, `elapsed` is below `duration`.
+        let range = auction.start_price - auction.reserve_price;
+        let discount = (range / duration).saturating_mul(elapsed) + (range % duration).saturating_mul(elapsed) / duration;
+        auction.start_price - discount
+    }
+
+    fn settle_auction(kitty_id: T::KittyIndex, now: T::BlockNumber) {
+        let auction = match Self::auction(kitty_id) {
+            Some(ref auction) if auction.end == now => auction.clone(),
+            // Cancelled, or a Dutch auction that was won before its end.
+            _ => return,
+        };
+        <Auctions<T>>::remove(kitty_id);
+
+        match auction.bid {
+            Some((bidder, amount)) => {
+                if amount >= auction.reserve_price && T::Currency::repatriate_reserved(&bidder, &auction.seller, amount).is_ok() {
+                    Self::do_transfer(&auction.seller, &bidder, kitty_id);
+
+                    Self::deposit_event(RawEvent::AuctionSettled(auction.seller, bidder, kitty_id, amount));
+                } else {
+                    // Below the reserve price, or the bid could not be paid out.
+                    T::Currency::unreserve(&bidder, amount);
+
+                    Self::deposit_event(RawEvent::AuctionFailed(auction.seller, kitty_id));
+                }
+            },
+            None => Self::deposit_event(RawEvent::AuctionFailed(auction.seller, kitty_id)),
+        }
+    }
+
+    fn remove_offer(kitty_id: T::KittyIndex, buyer: &T::AccountId) -> Option<Offer<BalanceOf<T>, T::BlockNumber>> {
+        let offer = <Offers<T>>::take((kitty_id, buyer.clone()))?;
+        <KittyOffers<T>>::mutate(kitty_id, |offers| offers.retain(|who| who != buyer));
+        Self::unschedule_offer(offer.expiry, kitty_id, buyer);
+        Some(offer)
+    }
 
-        Ok((kitty_id))
+    /// Takes an offer off the sweep of its expiry block, so replacing an offer does not pile up entries.
+    fn unschedule_offer(expiry: T::BlockNumber, kitty_id: T::KittyIndex, buyer: &T::AccountId) {
+        let remaining = <OffersExpiringAt<T>>::mutate(expiry, |offers| {
+            offers.retain(|(id, who)| *id != kitty_id || who != buyer);
+            offers.len()
+        });
+        if remaining == 0 {
+            <OffersExpiringAt<T>>::remove(expiry);
+        }
+    }
+
+    fn expire_offer(kitty_id: T::KittyIndex, buyer: T::AccountId, now: T::BlockNumber) {
+        match Self::offer((kitty_id, buyer.clone())) {
+            Some(ref offer) if offer.expiry == now => (),
+            // Entries leave the sweep together with their offers, this only guards against a mismatch.
+            _ => return,
+        }
+        if let Some(offer) = Self::remove_offer(kitty_id, &buyer) {
+            T::Currency::unreserve(&buyer, offer.amount);
+
+            Self::deposit_event(RawEvent::OfferExpired(buyer, kitty_id));
+        }
     }
 }
 
@@ -292,10 +1012,10 @@ mod tests {
 
 	use runtime_io::with_externalities;
 	use primitives::{H256, Blake2Hasher};
-	use support::{impl_outer_origin, assert_ok};
+	use support::{impl_outer_origin, assert_ok, assert_noop};
 	use runtime_primitives::{
 		BuildStorage,
-		traits::{BlakeTwo256, IdentityLookup},
+		traits::{BlakeTwo256, IdentityLookup, OnInitialize, OnFinalize},
 		testing::{Digest, DigestItem, Header}
 	};
 
@@ -332,22 +1052,30 @@ mod tests {
         type TransferPayment = ();
     }
 	impl Trait for Test {
+		type Event = ();
 		type KittyIndex = u32;
         type Currency = balances::Module<Test>;
 	}
 	type KittyModule = Module<Test>;
     type OwnedKittiesTest = OwnedKitties<Test>;
+    type Balances = balances::Module<Test>;
+    type System = system::Module<Test>;
 
 	// This function basically just builds a genesis storage key/value store according to
 	// our desired mockup.
 	fn new_test_ext() -> runtime_io::TestExternalities<Blake2Hasher> {
-		system::GenesisConfig::<Test>::default().build_storage().unwrap().0.into()
+		let mut t = system::GenesisConfig::<Test>::default().build_storage().unwrap().0;
+		t.extend(balances::GenesisConfig::<Test> {
+			balances: vec![(1, 100), (2, 100), (3, 100)],
+			..Default::default()
+		}.build_storage().unwrap().0);
+		t.into()
 	}
 
 	#[test]
 	fn it_works_for_default_value() {
 		with_externalities(&mut new_test_ext(), || {
-			OwnedKittiesTest::append(&0, 1);
+			OwnedKittiesList::<Test>::append(&0, 1);
 
             assert_eq!(OwnedKittiesTest::get(&(0, None)), Some(KittyLinkedItem {
                 prev: Some(1),
@@ -359,7 +1087,7 @@ mod tests {
                 next: None,
             }));
 
-            OwnedKittiesTest::append(&0, 2);
+            OwnedKittiesList::<Test>::append(&0, 2);
 
             assert_eq!(OwnedKittiesTest::get(&(0, None)), Some(KittyLinkedItem {
                 prev: Some(2),
@@ -376,7 +1104,7 @@ mod tests {
                 next: None,
             }));
 
-            OwnedKittiesTest::append(&0, 3);
+            OwnedKittiesList::<Test>::append(&0, 3);
 
             assert_eq!(OwnedKittiesTest::get(&(0, None)), Some(KittyLinkedItem {
                 prev: Some(3),
@@ -404,11 +1132,11 @@ mod tests {
     #[test]
     fn owned_kitties_can_remove_values() {
         with_externalities(&mut new_test_ext(), || {
-            OwnedKittiesTest::append(&0, 1);
-            OwnedKittiesTest::append(&0, 2);
-            OwnedKittiesTest::append(&0, 3);
+            OwnedKittiesList::<Test>::append(&0, 1);
+            OwnedKittiesList::<Test>::append(&0, 2);
+            OwnedKittiesList::<Test>::append(&0, 3);
 
-            OwnedKittiesTest::remove(&0, 2);
+            OwnedKittiesList::<Test>::remove(&0, 2);
 
             assert_eq!(OwnedKittiesTest::get(&(0, None)), Some(KittyLinkedItem {
                 prev: Some(3),
@@ -427,7 +1155,7 @@ mod tests {
                 next: None,
             }));
 
-            OwnedKittiesTest::remove(&0, 1);
+            OwnedKittiesList::<Test>::remove(&0, 1);
 
             assert_eq!(OwnedKittiesTest::get(&(0, None)), Some(KittyLinkedItem {
                 prev: Some(3),
@@ -443,7 +1171,7 @@ mod tests {
                 next: None,
             }));
 
-            OwnedKittiesTest::remove(&0, 3);
+            OwnedKittiesList::<Test>::remove(&0, 3);
 
             assert_eq!(OwnedKittiesTest::get(&(0, None)), Some(KittyLinkedItem {
                 prev: None,
@@ -457,6 +1185,513 @@ mod tests {
             assert_eq!(OwnedKittiesTest::get(&(0, Some(3))), None);
         });
     }
+
+    #[test]
+    fn english_auction_settles_to_highest_bidder() {
+        with_externalities(&mut new_test_ext(), || {
+            System::set_block_number(1);
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::create_auction(Origin::signed(1), 0, AuctionKind::English, 10, 20, 5));
+
+            assert_noop!(KittyModule::bid(Origin::signed(2), 0, 5), "Bid is below start price");
+            assert_ok!(KittyModule::bid(Origin::signed(2), 0, 15));
+            assert_noop!(KittyModule::bid(Origin::signed(3), 0, 15), "Bid is too low");
+            assert_ok!(KittyModule::bid(Origin::signed(3), 0, 30));
+
+            // The outbid funds are released.
+            assert_eq!(Balances::reserved_balance(&2), 0);
+            assert_eq!(Balances::reserved_balance(&3), 30);
+            assert_noop!(KittyModule::transfer(Origin::signed(1), 2, 0), "Kitty is in auction");
+
+            <KittyModule as OnFinalize<u64>>::on_finalize(5);
+
+            assert_eq!(KittyModule::auction(0), None);
+            assert_eq!(KittyModule::kitty_owner(0), Some(3));
+            assert_eq!(Balances::free_balance(&1), 130);
+            assert_eq!(Balances::reserved_balance(&3), 0);
+        });
+    }
+
+    #[test]
+    fn english_auction_below_reserve_refunds_bidder() {
+        with_externalities(&mut new_test_ext(), || {
+            System::set_block_number(1);
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::create_auction(Origin::signed(1), 0, AuctionKind::English, 10, 50, 5));
+            assert_ok!(KittyModule::bid(Origin::signed(2), 0, 20));
+
+            <KittyModule as OnFinalize<u64>>::on_finalize(5);
+
+            assert_eq!(KittyModule::auction(0), None);
+            assert_eq!(KittyModule::kitty_owner(0), Some(1));
+            assert_eq!(Balances::free_balance(&2), 100);
+        });
+    }
+
+    #[test]
+    fn unpayable_auction_is_not_settled() {
+        with_externalities(&mut new_test_ext(), || {
+            System::set_block_number(1);
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            // Account 4 has no balance, funds can not be repatriated to it.
+            assert_ok!(KittyModule::transfer(Origin::signed(1), 4, 0));
+            assert_ok!(KittyModule::create_auction(Origin::signed(4), 0, AuctionKind::English, 10, 10, 5));
+            assert_ok!(KittyModule::bid(Origin::signed(2), 0, 20));
+
+            <KittyModule as OnFinalize<u64>>::on_finalize(5);
+
+            assert_eq!(KittyModule::auction(0), None);
+            assert_eq!(KittyModule::kitty_owner(0), Some(4));
+            assert_eq!(Balances::free_balance(&2), 100);
+            assert_eq!(Balances::reserved_balance(&2), 0);
+        });
+    }
+
+    #[test]
+    fn dutch_price_does_not_overflow() {
+        with_externalities(&mut new_test_ext(), || {
+            System::set_block_number(1);
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::create_auction(Origin::signed(1), 0, AuctionKind::Dutch, 4_000_000_000, 0, 11));
+
+            let auction = KittyModule::auction(0).unwrap();
+            assert_eq!(KittyModule::dutch_price(&auction, 6), 2_000_000_000);
+            assert_eq!(KittyModule::dutch_price(&auction, 10), 400_000_000);
+        });
+    }
+
+    #[test]
+    fn dutch_auction_price_falls_and_first_bid_wins() {
+        with_externalities(&mut new_test_ext(), || {
+            System::set_block_number(1);
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::create_auction(Origin::signed(1), 0, AuctionKind::Dutch, 90, 10, 11));
+
+            System::set_block_number(6);
+            assert_noop!(KittyModule::bid(Origin::signed(2), 0, 49), "Bid is below current price");
+            assert_ok!(KittyModule::bid(Origin::signed(2), 0, 60));
+            assert_noop!(KittyModule::bid(Origin::signed(3), 0, 90), "Auction has ended");
+
+            <KittyModule as OnFinalize<u64>>::on_finalize(6);
+
+            assert_eq!(KittyModule::kitty_owner(0), Some(2));
+            assert_eq!(Balances::free_balance(&2), 50);
+            assert_eq!(Balances::free_balance(&1), 150);
+
+            // The stale entry at the original end block is ignored.
+            <KittyModule as OnFinalize<u64>>::on_finalize(11);
+            assert_eq!(KittyModule::kitty_owner(0), Some(2));
+        });
+    }
+
+    #[test]
+    fn only_seller_can_cancel_auction_without_bids() {
+        with_externalities(&mut new_test_ext(), || {
+            System::set_block_number(1);
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::create_auction(Origin::signed(1), 0, AuctionKind::English, 10, 10, 5));
+
+            assert_noop!(KittyModule::cancel_auction(Origin::signed(2), 0), "Only seller can cancel auction");
+            assert_ok!(KittyModule::bid(Origin::signed(2), 0, 10));
+            assert_noop!(KittyModule::cancel_auction(Origin::signed(1), 0), "Auction already has a bid");
+        });
+    }
+
+    #[test]
+    fn accepted_offer_moves_funds_and_kitty() {
+        with_externalities(&mut new_test_ext(), || {
+            System::set_block_number(1);
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+
+            assert_noop!(KittyModule::make_offer(Origin::signed(1), 0, 10, 5), "Can not make offer for own kitty");
+            assert_ok!(KittyModule::make_offer(Origin::signed(2), 0, 10, 5));
+            assert_ok!(KittyModule::make_offer(Origin::signed(3), 0, 20, 5));
+            assert_eq!(Balances::reserved_balance(&2), 10);
+
+            assert_noop!(KittyModule::accept_offer(Origin::signed(2), 0, 3), "Only owner can accept offer");
+            assert_ok!(KittyModule::accept_offer(Origin::signed(1), 0, 3));
+
+            assert_eq!(KittyModule::kitty_owner(0), Some(3));
+            assert_eq!(Balances::free_balance(&1), 120);
+            assert_eq!(Balances::free_balance(&3), 80);
+            assert_eq!(KittyModule::offer((0, 3)), None);
+            assert_eq!(KittyModule::kitty_offers(0), vec![2]);
+        });
+    }
+
+    #[test]
+    fn replacing_offer_reserves_new_amount() {
+        with_externalities(&mut new_test_ext(), || {
+            System::set_block_number(1);
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::make_offer(Origin::signed(2), 0, 10, 5));
+            assert_ok!(KittyModule::make_offer(Origin::signed(2), 0, 30, 8));
+
+            assert_eq!(Balances::reserved_balance(&2), 30);
+            assert_eq!(KittyModule::kitty_offers(0), vec![2]);
+
+            // The replaced offer is taken off the sweep, the new one is swept once.
+            assert_eq!(KittyModule::offers_expiring_at(5), Vec::<(u32, u64)>::new());
+            assert_eq!(KittyModule::offers_expiring_at(8), vec![(0, 2)]);
+            <KittyModule as OnFinalize<u64>>::on_finalize(5);
+            assert_eq!(Balances::reserved_balance(&2), 30);
+
+            assert_ok!(KittyModule::make_offer(Origin::signed(2), 0, 40, 8));
+            assert_eq!(KittyModule::offers_expiring_at(8), vec![(0, 2)]);
+
+            assert_ok!(KittyModule::withdraw_offer(Origin::signed(2), 0));
+            assert_eq!(Balances::reserved_balance(&2), 0);
+            assert_eq!(KittyModule::offers_expiring_at(8), Vec::<(u32, u64)>::new());
+            assert_noop!(KittyModule::withdraw_offer(Origin::signed(2), 0), "Offer does not exist");
+        });
+    }
+
+    #[test]
+    fn expired_offers_are_swept() {
+        with_externalities(&mut new_test_ext(), || {
+            System::set_block_number(1);
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::make_offer(Origin::signed(2), 0, 10, 5));
+
+            <KittyModule as OnFinalize<u64>>::on_finalize(5);
+
+            System::set_block_number(6);
+            assert_eq!(Balances::reserved_balance(&2), 0);
+            assert_eq!(KittyModule::offer((0, 2)), None);
+            assert_eq!(KittyModule::kitty_offers(0), Vec::<u64>::new());
+            assert_noop!(KittyModule::accept_offer(Origin::signed(1), 0, 2), "Offer does not exist");
+        });
+    }
+
+    #[test]
+    fn bred_kitty_inherits_parent_alleles() {
+        with_externalities(&mut new_test_ext(), || {
+            <Kitties<Test>>::insert(0, Kitty { dna: [0x81; 16], parents: None, generation: 0, birth: 0 });
+            <Kitties<Test>>::insert(1, Kitty { dna: [0x02; 16], parents: None, generation: 0, birth: 0 });
+            <KittiesCount<Test>>::put(2);
+            <KittyOwners<Test>>::insert(0, 1);
+            <KittyOwners<Test>>::insert(1, 1);
+
+            assert_ok!(KittyModule::breed(Origin::signed(1), 0, 1));
+
+            // Without mutation every locus holds the dominant allele of the first and the recessive one of the second parent.
+            let child = KittyModule::kitty(2).unwrap().dna;
+            for locus in 0..genetics::LOCI {
+                assert_eq!((child[locus * 2], child[locus * 2 + 1]), (0x81, 0x02));
+            }
+            assert_eq!(KittyModule::kitty_traits(2).unwrap().fur, genetics::Gene { variant: 1, dominant: true });
+            assert_eq!(KittyModule::kitty_traits(3), None);
+        });
+    }
+
+    #[test]
+    fn only_root_can_set_mutation_rate() {
+        with_externalities(&mut new_test_ext(), || {
+            assert!(KittyModule::set_mutation_rate(Origin::signed(1), Permill::from_percent(5)).is_err());
+            assert_ok!(KittyModule::set_mutation_rate(Origin::ROOT, Permill::from_percent(5)));
+            assert_eq!(KittyModule::mutation_rate(), Permill::from_percent(5));
+        });
+    }
+
+    #[test]
+    fn breeding_records_lineage_and_cooldown() {
+        with_externalities(&mut new_test_ext(), || {
+            System::set_block_number(1);
+            assert_ok!(KittyModule::set_breeding_cooldown(Origin::ROOT, 10));
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::breed(Origin::signed(1), 0, 1));
+
+            let child = KittyModule::kitty(2).unwrap();
+            assert_eq!(child.parents, Some((0, 1)));
+            assert_eq!(child.generation, 1);
+            assert_eq!(child.birth, 1);
+            assert_eq!(KittyModule::kitty_cooldown(0), 11);
+
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_noop!(KittyModule::breed(Origin::signed(1), 0, 3), "Kitty1 is on breeding cooldown");
+
+            // A generation 1 parent waits twice as long.
+            System::set_block_number(11);
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::breed(Origin::signed(1), 2, 4));
+            assert_eq!(KittyModule::kitty_cooldown(2), 31);
+            assert_eq!(KittyModule::kitty(5).unwrap().generation, 2);
+        });
+    }
+
+    #[test]
+    fn inbreeding_is_rejected() {
+        with_externalities(&mut new_test_ext(), || {
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::breed(Origin::signed(1), 0, 1));
+            assert_ok!(KittyModule::breed(Origin::signed(1), 0, 2));
+
+            // Parent and child.
+            assert_noop!(KittyModule::breed(Origin::signed(1), 3, 0), "Parents are related");
+            assert_noop!(KittyModule::breed(Origin::signed(1), 1, 3), "Parents are related");
+            // Half siblings.
+            assert_noop!(KittyModule::breed(Origin::signed(1), 3, 4), "Parents are related");
+        });
+    }
+
+    #[test]
+    fn legacy_kitty_decodes_without_lineage() {
+        let legacy = [7u8; 16].encode();
+        let kitty = KittyOf::<Test>::decode(&mut &legacy[..]).unwrap();
+
+        assert_eq!(kitty, Kitty { dna: [7u8; 16], parents: None, generation: 0, birth: 0 });
+        assert_eq!(KittyOf::<Test>::decode(&mut &kitty.encode()[..]), Some(kitty));
+    }
+
+    #[test]
+    fn storage_is_migrated_once() {
+        with_externalities(&mut new_test_ext(), || {
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            // Kitties were not counted before storage version 2.
+            <OwnedKittiesCount<Test>>::remove(1);
+            assert_eq!(KittyModule::storage_version(), 0);
+
+            <KittyModule as OnInitialize<u64>>::on_initialize(1);
+
+            assert_eq!(KittyModule::storage_version(), STORAGE_VERSION);
+            assert!(KittyModule::kitty(0).is_some());
+            assert_eq!(KittyModule::owned_kitties_count(1), 1);
+
+            <KittyModule as OnInitialize<u64>>::on_initialize(2);
+            assert_eq!(KittyModule::owned_kitties_count(1), 1);
+        });
+    }
+
+    #[test]
+    fn migration_runs_over_several_blocks() {
+        with_externalities(&mut new_test_ext(), || {
+            for _ in 0..=MIGRATION_BATCH_SIZE {
+                assert_ok!(KittyModule::create(Origin::signed(1)));
+            }
+            <OwnedKittiesCount<Test>>::remove(1);
+
+            <KittyModule as OnInitialize<u64>>::on_initialize(1);
+            assert_eq!(KittyModule::storage_version(), 0);
+            assert_eq!(KittyModule::migration_cursor(), Some((MIGRATION_BATCH_SIZE, MIGRATION_BATCH_SIZE + 1)));
+            assert_eq!(KittyModule::owned_kitties_count(1), MIGRATION_BATCH_SIZE);
+
+            // Kitties change hands between the blocks of the migration.
+            assert_ok!(KittyModule::transfer(Origin::signed(1), 2, 0));
+            assert_ok!(KittyModule::transfer(Origin::signed(1), 2, MIGRATION_BATCH_SIZE));
+            assert_ok!(KittyModule::burn(Origin::signed(1), 1));
+            assert_ok!(KittyModule::create(Origin::signed(3)));
+
+            <KittyModule as OnInitialize<u64>>::on_initialize(2);
+            assert_eq!(KittyModule::storage_version(), STORAGE_VERSION);
+            assert_eq!(KittyModule::migration_cursor(), None);
+            assert_eq!(KittyModule::owned_kitties_count(1), MIGRATION_BATCH_SIZE - 2);
+            assert_eq!(KittyModule::owned_kitties_count(2), 2);
+            assert_eq!(KittyModule::owned_kitties_count(3), 1);
+        });
+    }
+
+    #[test]
+    fn breeding_with_listed_sire_pays_owner() {
+        with_externalities(&mut new_test_ext(), || {
+            System::set_block_number(1);
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::create(Origin::signed(2)));
+
+            assert_noop!(KittyModule::breed_with_sire(Origin::signed(2), 1, 0, 10), "Kitty is not listed as sire");
+            assert_noop!(KittyModule::list_sire(Origin::signed(2), 0, 10, 5), "Only owner can list kitty as sire");
+            assert_ok!(KittyModule::list_sire(Origin::signed(1), 0, 10, 5));
+
+            assert_noop!(KittyModule::breed_with_sire(Origin::signed(2), 1, 0, 9), "Sire fee above maximum");
+            assert_ok!(KittyModule::breed_with_sire(Origin::signed(2), 1, 0, 10));
+
+            assert_eq!(Balances::free_balance(&1), 110);
+            assert_eq!(Balances::free_balance(&2), 90);
+            assert_eq!(KittyModule::kitty_owner(2), Some(2));
+            assert_eq!(KittyModule::kitty_owner(0), Some(1));
+            assert_eq!(KittyModule::kitty(2).unwrap().parents, Some((1, 0)));
+        });
+    }
+
+    #[test]
+    fn sire_listing_expires_and_can_be_cancelled() {
+        with_externalities(&mut new_test_ext(), || {
+            System::set_block_number(1);
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::create(Origin::signed(2)));
+            assert_ok!(KittyModule::list_sire(Origin::signed(1), 0, 10, 5));
+
+            System::set_block_number(6);
+            assert_noop!(KittyModule::breed_with_sire(Origin::signed(2), 1, 0, 10), "Sire listing expired");
+
+            assert_noop!(KittyModule::cancel_sire(Origin::signed(2), 0), "Only owner can cancel sire listing");
+            assert_ok!(KittyModule::cancel_sire(Origin::signed(1), 0));
+            assert_eq!(KittyModule::sire_listing(0), None);
+        });
+    }
+
+    #[test]
+    fn failed_sire_breeding_charges_nothing() {
+        with_externalities(&mut new_test_ext(), || {
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::create(Origin::signed(2)));
+            assert_ok!(KittyModule::create(Origin::signed(3)));
+            assert_ok!(KittyModule::list_sire(Origin::signed(1), 0, 10, 5));
+
+            // Kitty 2 is not owned by the caller.
+            assert_noop!(KittyModule::breed_with_sire(Origin::signed(2), 2, 0, 10), "Not onwer of kitty1");
+            assert_eq!(Balances::free_balance(&2), 100);
+
+            assert_ok!(KittyModule::set_mint_deposit(Origin::ROOT, 1));
+            assert_ok!(KittyModule::list_sire(Origin::signed(1), 0, u32::max_value(), 5));
+            assert_noop!(
+                KittyModule::breed_with_sire(Origin::signed(2), 1, 0, u32::max_value()),
+                "Sire fee and creation deposit overflow"
+            );
+
+            // Transferring the sire ends the listing.
+            assert_ok!(KittyModule::transfer(Origin::signed(1), 3, 0));
+            assert_eq!(KittyModule::sire_listing(0), None);
+        });
+    }
+
+    #[test]
+    fn failed_sire_fee_returns_creation_deposit() {
+        with_externalities(&mut new_test_ext(), || {
+            use support::traits::{LockableCurrency, WithdrawReason};
+
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::create(Origin::signed(2)));
+            assert_ok!(KittyModule::list_sire(Origin::signed(1), 0, 10, 5));
+            assert_ok!(KittyModule::set_mint_deposit(Origin::ROOT, 10));
+
+            // The deposit can be reserved, but the locked funds can not be transferred.
+            Balances::set_lock(*b"kittylck", &2, 85, u64::max_value(), WithdrawReason::Transfer.into());
+            assert!(KittyModule::breed_with_sire(Origin::signed(2), 1, 0, 10).is_err());
+
+            assert_eq!(Balances::free_balance(&2), 100);
+            assert_eq!(Balances::reserved_balance(&2), 0);
+            assert_eq!(KittyModule::kitty_deposit(2), None);
+            assert_eq!(KittyModule::kitty_owner(2), None);
+        });
+    }
+
+    #[test]
+    fn owned_kitties_are_paged_in_list_order() {
+        with_externalities(&mut new_test_ext(), || {
+            for _ in 0..5 {
+                assert_ok!(KittyModule::create(Origin::signed(1)));
+            }
+            assert_ok!(KittyModule::transfer(Origin::signed(1), 2, 1));
+            assert_ok!(KittyModule::ask(Origin::signed(1), 3, Some(8)));
+
+            let page = KittyModule::owned_kitties_page(&1, None, 2);
+            assert_eq!(page.kitties.iter().map(|k| k.id).collect::<Vec<_>>(), vec![0, 2]);
+            assert_eq!(page.next, Some(3));
+            assert_eq!(page.total, 4);
+
+            let page = KittyModule::owned_kitties_page(&1, page.next, 2);
+            assert_eq!(page.kitties.iter().map(|k| k.id).collect::<Vec<_>>(), vec![3, 4]);
+            assert_eq!(page.kitties[0].price, Some(8));
+            assert_eq!(page.kitties[0].owner, 1);
+            assert_eq!(page.kitties[0].dna, KittyModule::kitty(3).unwrap().dna);
+            assert_eq!(page.next, None);
+
+            assert_eq!(KittyModule::owned_kitties_count(&2), 1);
+        });
+    }
+
+    #[test]
+    fn burn_removes_kitty_and_refunds_offers() {
+        with_externalities(&mut new_test_ext(), || {
+            System::set_block_number(1);
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::ask(Origin::signed(1), 0, Some(10)));
+            assert_ok!(KittyModule::make_offer(Origin::signed(2), 0, 20, 5));
+
+            assert_noop!(KittyModule::burn(Origin::signed(2), 0), "Only owner can burn kitty");
+            assert_ok!(KittyModule::burn(Origin::signed(1), 0));
+
+            assert_eq!(KittyModule::kitty(0), None);
+            assert_eq!(KittyModule::kitty_owner(0), None);
+            assert_eq!(KittyModule::kitty_price(0), None);
+            assert_eq!(KittyModule::offer((0, 2)), None);
+            assert_eq!(Balances::reserved_balance(&2), 0);
+            assert_eq!(KittyModule::owned_kitties_count(1), 1);
+            assert_eq!(KittyModule::owned_kitties_page(&1, None, 10).kitties[0].id, 1);
+            // Ids are not reused.
+            assert_eq!(KittyModule::kitties_count(), 2);
+        });
+    }
+
+    #[test]
+    fn gift_moves_kitty_with_bounded_memo() {
+        with_externalities(&mut new_test_ext(), || {
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::ask(Origin::signed(1), 0, Some(10)));
+
+            assert_noop!(KittyModule::gift(Origin::signed(1), 2, 0, vec![0; MAX_MEMO_LEN + 1]), "Memo is too long");
+            assert_noop!(KittyModule::gift(Origin::signed(2), 3, 0, b"hi".to_vec()), "Only owner can gift kitty");
+            assert_ok!(KittyModule::gift(Origin::signed(1), 2, 0, b"happy birthday".to_vec()));
+
+            assert_eq!(KittyModule::kitty_owner(0), Some(2));
+            assert_eq!(KittyModule::kitty_price(0), None);
+        });
+    }
+
+    #[test]
+    fn minting_reserves_deposit_refunded_on_burn() {
+        with_externalities(&mut new_test_ext(), || {
+            assert!(KittyModule::set_mint_deposit(Origin::signed(1), 10).is_err());
+            assert_ok!(KittyModule::set_mint_deposit(Origin::ROOT, 10));
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_eq!(Balances::reserved_balance(&1), 20);
+
+            // The deposit goes back to the minter, whoever burns the kitty.
+            assert_ok!(KittyModule::transfer(Origin::signed(1), 2, 0));
+            assert_ok!(KittyModule::burn(Origin::signed(2), 0));
+            assert_eq!(Balances::reserved_balance(&1), 10);
+            assert_eq!(KittyModule::kitty_deposit(0), None);
+
+            assert_ok!(KittyModule::set_mint_deposit(Origin::ROOT, 200));
+            assert_eq!(KittyModule::ensure_can_mint(&1), Err(MintError::InsufficientDeposit));
+            assert_noop!(KittyModule::create(Origin::signed(1)), "Can not reserve creation deposit");
+        });
+    }
+
+    #[test]
+    fn minting_is_capped_per_account() {
+        with_externalities(&mut new_test_ext(), || {
+            assert_ok!(KittyModule::set_max_kitties_per_account(Origin::ROOT, 2));
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+
+            assert_eq!(KittyModule::ensure_can_mint(&1), Err(MintError::TooManyKitties));
+            assert_noop!(KittyModule::create(Origin::signed(1)), "Too many kitties");
+            assert_noop!(KittyModule::breed(Origin::signed(1), 0, 1), "Too many kitties");
+
+            // Transfers are not capped, and make room for minting again.
+            assert_ok!(KittyModule::transfer(Origin::signed(1), 2, 0));
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+        });
+    }
+
+    #[test]
+    fn unknown_cursor_yields_empty_page() {
+        with_externalities(&mut new_test_ext(), || {
+            assert_ok!(KittyModule::create(Origin::signed(1)));
+            assert_ok!(KittyModule::create(Origin::signed(2)));
+
+            let page = KittyModule::owned_kitties_page(&1, Some(1), 10);
+            assert!(page.kitties.is_empty());
+            assert_eq!(page.next, None);
+            assert_eq!(page.total, 1);
+        });
+    }
 }
 
 /// tests for this module
@@ -33,6 +33,7 @@ pub use balances::Call as BalancesCall;
 pub use runtime_primitives::{Permill, Perbill};
 pub use timestamp::BlockPeriod;
 pub use support::{StorageValue, construct_runtime};
+pub use system::EventRecord;
 
 /// The type that is used for identifying authorities.
 pub type AuthorityId = <AuthoritySignature as Verify>::Signer;
@@ -55,13 +56,21 @@ pub type BlockNumber = u64;
 /// Index of an account's extrinsic in the chain.
 pub type Nonce = u64;
 
+/// Balance of an account.
+pub type Balance = u128;
+
+/// Index of a kitty.
+pub type KittyIndex = u32;
+
 /// Used for the module template in `./template.rs`
 mod template;
 
-mod kitties;
+pub mod kitties;
 
 mod linked_item;
 
+pub mod genetics;
+
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
 /// the specifics of the runtime. They can then be made to be agnostic over specific formats
 /// of data like extrinsics, allowing for them to continue syncing the network through upgrades
@@ -172,7 +181,7 @@ impl timestamp::Trait for Runtime {
 
 impl balances::Trait for Runtime {
 	/// The type for recording an account's balance.
-	type Balance = u128;
+	type Balance = Balance;
 	/// What to do if an account's free balance gets zeroed.
 	type OnFreeBalanceZero = ();
 	/// What to do if a new account is created.
@@ -198,7 +207,7 @@ impl template::Trait for Runtime {
 
 impl kitties::Trait for Runtime {
 	type Event = Event;
-	type KittyIndex = u32;
+	type KittyIndex = KittyIndex;
 	type Currency = Balances;
 }
 
@@ -217,7 +226,7 @@ construct_runtime!(
 		Sudo: sudo,
 		// Used for the module template in `./template.rs`
 		TemplateModule: template::{Module, Call, Storage, Event<T>},
-		Kitties: kitties::{Module, Call, Storage, Event<T>},
+		Kitties: kitties::{Module, Call, Storage, Event<T>, Config<T>},
 	}
 );
 
@@ -309,4 +318,22 @@ impl_runtime_apis! {
 			Consensus::authorities()
 		}
 	}
+
+	impl kitties::KittiesApi<Block, AccountId, KittyIndex, Balance> for Runtime {
+		fn kitty_traits(kitty_id: KittyIndex) -> Option<genetics::KittyTraits> {
+			Kitties::kitty_traits(kitty_id)
+		}
+
+		fn kitties_count() -> KittyIndex {
+			Kitties::kitties_count()
+		}
+
+		fn owned_kitties_count(account: AccountId) -> u64 {
+			u64::from(Kitties::owned_kitties_count(&account))
+		}
+
+		fn owned_kitties(account: AccountId, cursor: Option<KittyIndex>, limit: u32) -> kitties::KittiesPage<AccountId, KittyIndex, Balance> {
+			Kitties::owned_kitties_page(&account, cursor, limit)
+		}
+	}
 }
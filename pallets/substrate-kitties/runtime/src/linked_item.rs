@@ -1,6 +1,7 @@
 use support::{StorageMap, Parameter};
 use runtime_primitives::traits::Member;
 use parity_codec::{Encode, Decode, Input, Output};
+use rstd::prelude::*;
 
 #[cfg_attr(feature = "std", derive(Debug, PartialEq, Eq))]
 // #[derive(Encode, Decode)]
@@ -9,21 +10,22 @@ pub struct LinkedItem<Item> {
 	pub next: Option<Item>,
 }
 
-pub struct LinkedList<Storage, Key, Item>(rstd::marker::PhantomData<(Storage, Key, Item)>);
+/// A double linked list per key, stored as one `LinkedItem` per value in `Storage`.
+///
+/// The head of a list is stored under `(key, None)`, its `next` is the first value and its
+/// `prev` the last one. `LenStorage` keeps the number of values of every list.
+pub struct LinkedList<Storage, LenStorage, Key, Item>(rstd::marker::PhantomData<(Storage, LenStorage, Key, Item)>);
 
-impl<Storage, Key, Value> LinkedList<Storage, Key, Value> where
+impl<Storage, LenStorage, Key, Value> LinkedList<Storage, LenStorage, Key, Value> where
   Value: Parameter + Member + Copy,
   Key: Parameter,
   Storage: StorageMap<(Key, Option<Value>), LinkedItem<Value>, Query = Option<LinkedItem<Value>>>,
+  LenStorage: StorageMap<Key, u32, Query = u32>,
 {
 	fn read_head(key: &Key) -> LinkedItem<Value> {
 		Self::read(key, None)
 	}
 
-	fn write_head(account: &Key, item: LinkedItem<Value>) {
-		Self::write(account, None, item);
-	}
-
 	fn read(key: &Key, value: Option<Value>) -> LinkedItem<Value> {
 		Storage::get(&(key.clone(), value)).unwrap_or_else(|| LinkedItem {
 			prev: None,
@@ -35,30 +37,82 @@ impl<Storage, Key, Value> LinkedList<Storage, Key, Value> where
 		Storage::insert(&(key.clone(), value), item);
 	}
 
-	pub fn append(key: &Key, value: Value) {
+	/// Links `value` between `prev` and `next`, `None` stands for the head.
+	fn link(key: &Key, prev: Option<Value>, next: Option<Value>, value: Value) {
+		let mut prev_item = Self::read(key, prev);
+		prev_item.next = Some(value);
+		Self::write(key, prev, prev_item);
+
+		// Read after writing `prev`, both are the head if the list is empty.
+		let mut next_item = Self::read(key, next);
+		next_item.prev = Some(value);
+		Self::write(key, next, next_item);
+
+		Self::write(key, Some(value), LinkedItem { prev, next });
+		LenStorage::mutate(key, |len| *len += 1);
+	}
+
+	/// Number of values in the list.
+	pub fn len(key: &Key) -> u32 {
+		LenStorage::get(key)
+	}
+
+	pub fn is_empty(key: &Key) -> bool {
+		Self::len(key) == 0
+	}
+
+	pub fn contains(key: &Key, value: Value) -> bool {
+		Storage::exists(&(key.clone(), Some(value)))
+	}
+
+	pub fn front(key: &Key) -> Option<Value> {
+		Self::read_head(key).next
+	}
+
+	pub fn back(key: &Key) -> Option<Value> {
+		Self::read_head(key).prev
+	}
+
+	/// Adds `value` at the end of the list. Returns `false` and leaves the list unchanged if it already contains `value`.
+	pub fn append(key: &Key, value: Value) -> bool {
+		if Self::contains(key, value) {
+			return false;
+		}
 		let head = Self::read_head(key);
-		let new_head = LinkedItem {
-			prev: Some(value),
-			next: head.next,
-		};
-
-		Self::write_head(key, new_head);
-
-		let prev = Self::read(key, head.prev);
-		let new_prev = LinkedItem {
-			prev: prev.prev,
-			next: Some(value),
-		};
-		Self::write(key, head.prev, new_prev);
-
-		let item = LinkedItem {
-			prev: head.prev,
-			next: None,
-		};
-		Self::write(key, Some(value), item);
+		Self::link(key, head.prev, None, value);
+		true
 	}
 
-	pub fn remove(key: &Key, value: Value) {
+	/// Adds `value` right after `anchor`. Returns `false` if `anchor` is not in the list or `value` already is.
+	pub fn insert_after(key: &Key, anchor: Value, value: Value) -> bool {
+		if Self::contains(key, value) {
+			return false;
+		}
+		match Storage::get(&(key.clone(), Some(anchor))) {
+			Some(item) => {
+				Self::link(key, Some(anchor), item.next, value);
+				true
+			},
+			None => false,
+		}
+	}
+
+	/// Adds `value` right before `anchor`. Returns `false` if `anchor` is not in the list or `value` already is.
+	pub fn insert_before(key: &Key, anchor: Value, value: Value) -> bool {
+		if Self::contains(key, value) {
+			return false;
+		}
+		match Storage::get(&(key.clone(), Some(anchor))) {
+			Some(item) => {
+				Self::link(key, item.prev, Some(anchor), value);
+				true
+			},
+			None => false,
+		}
+	}
+
+	/// Removes `value` from the list. Returns `false` if it is not in the list.
+	pub fn remove(key: &Key, value: Value) -> bool {
 		if let Some(item) = Storage::take(&(key.clone(), Some(value))) {
 			let prev = Self::read(key, item.prev);
 			let new_prev = LinkedItem {
@@ -75,7 +129,75 @@ impl<Storage, Key, Value> LinkedList<Storage, Key, Value> where
 			};
 
 			Self::write(key, item.next, new_next);
+
+			LenStorage::mutate(key, |len| *len = len.saturating_sub(1));
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Removes and returns the first value of the list.
+	pub fn pop_front(key: &Key) -> Option<Value> {
+		let value = Self::front(key)?;
+		Self::remove(key, value);
+		Some(value)
+	}
+
+	/// Iterates over the list from the first value. The iterator reads one value from storage per
+	/// step, bound it with `take` to limit the work done.
+	pub fn iter(key: &Key) -> Iter<Storage, Key, Value> {
+		Iter {
+			key: key.clone(),
+			next: Self::front(key),
+			_phantom: Default::default(),
+		}
+	}
+
+	/// Iterates over the list starting at `value`, empty if `value` is not in the list.
+	pub fn iter_from(key: &Key, value: Value) -> Iter<Storage, Key, Value> {
+		Iter {
+			key: key.clone(),
+			next: if Self::contains(key, value) { Some(value) } else { None },
+			_phantom: Default::default(),
+		}
+	}
+
+	/// Removes every value and the head of the list, returning the values in order.
+	pub fn take_all(key: &Key) -> Vec<Value> {
+		let values: Vec<Value> = Self::iter(key).collect();
+		for value in &values {
+			Storage::remove(&(key.clone(), Some(*value)));
 		}
+		Storage::remove(&(key.clone(), None));
+		LenStorage::remove(key);
+		values
+	}
+
+	/// Removes every value and the head of the list.
+	pub fn clear(key: &Key) {
+		Self::take_all(key);
+	}
+}
+
+/// Iterator over the values of a `LinkedList`.
+pub struct Iter<Storage, Key, Value> {
+	key: Key,
+	next: Option<Value>,
+	_phantom: rstd::marker::PhantomData<Storage>,
+}
+
+impl<Storage, Key, Value> Iterator for Iter<Storage, Key, Value> where
+  Value: Parameter + Member + Copy,
+  Key: Parameter,
+  Storage: StorageMap<(Key, Option<Value>), LinkedItem<Value>, Query = Option<LinkedItem<Value>>>,
+{
+	type Item = Value;
+
+	fn next(&mut self) -> Option<Value> {
+		let value = self.next.take()?;
+		self.next = Storage::get(&(self.key.clone(), Some(value))).and_then(|item| item.next);
+		Some(value)
 	}
 }
 
@@ -96,3 +218,168 @@ impl<Item> Encode for LinkedItem<Item> where Item: Encode
         dest.write(&Encode::encode(&self.next));
     }
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use runtime_io::with_externalities;
+	use primitives::{H256, Blake2Hasher};
+	use support::{decl_module, decl_storage, impl_outer_origin};
+	use runtime_primitives::{
+		BuildStorage,
+		traits::{BlakeTwo256, IdentityLookup},
+		testing::{Digest, DigestItem, Header}
+	};
+
+	impl_outer_origin! {
+		pub enum Origin for Test {}
+	}
+
+	#[derive(Clone, Eq, PartialEq, Debug)]
+	pub struct Test;
+	impl system::Trait for Test {
+		type Origin = Origin;
+		type Index = u64;
+		type BlockNumber = u64;
+		type Hash = H256;
+		type Hashing = BlakeTwo256;
+		type Digest = Digest;
+		type AccountId = u64;
+		type Lookup = IdentityLookup<Self::AccountId>;
+		type Header = Header;
+		type Event = ();
+		type Log = DigestItem;
+	}
+
+	pub trait Trait: system::Trait {}
+	impl Trait for Test {}
+
+	decl_module! {
+		pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+	}
+
+	decl_storage! {
+		trait Store for Module<T: Trait> as LinkedListTest {
+			pub Items: map (u64, Option<u32>) => Option<LinkedItem<u32>>;
+			pub ItemsCount: map u64 => u32;
+		}
+	}
+
+	type List = LinkedList<Items<Test>, ItemsCount<Test>, u64, u32>;
+
+	fn new_test_ext() -> runtime_io::TestExternalities<Blake2Hasher> {
+		system::GenesisConfig::<Test>::default().build_storage().unwrap().0.into()
+	}
+
+	fn values(key: u64) -> Vec<u32> {
+		List::iter(&key).collect()
+	}
+
+	#[test]
+	fn append_keeps_order_and_length() {
+		with_externalities(&mut new_test_ext(), || {
+			assert!(List::is_empty(&0));
+			assert!(List::append(&0, 1));
+			assert!(List::append(&0, 2));
+			assert!(List::append(&0, 3));
+
+			assert_eq!(values(0), vec![1, 2, 3]);
+			assert_eq!(List::len(&0), 3);
+			assert_eq!(List::front(&0), Some(1));
+			assert_eq!(List::back(&0), Some(3));
+			assert!(List::contains(&0, 2));
+			assert!(!List::contains(&1, 2));
+			assert!(List::is_empty(&1));
+		});
+	}
+
+	#[test]
+	fn duplicate_append_is_rejected() {
+		with_externalities(&mut new_test_ext(), || {
+			List::append(&0, 1);
+			List::append(&0, 2);
+
+			assert!(!List::append(&0, 1));
+			assert_eq!(values(0), vec![1, 2]);
+			assert_eq!(List::len(&0), 2);
+		});
+	}
+
+	#[test]
+	fn insert_after_and_before_anchor() {
+		with_externalities(&mut new_test_ext(), || {
+			List::append(&0, 1);
+			List::append(&0, 4);
+
+			assert!(List::insert_after(&0, 1, 2));
+			assert!(List::insert_before(&0, 4, 3));
+			assert!(List::insert_before(&0, 1, 0));
+			assert!(List::insert_after(&0, 4, 5));
+			assert_eq!(values(0), vec![0, 1, 2, 3, 4, 5]);
+			assert_eq!(List::len(&0), 6);
+			assert_eq!(List::back(&0), Some(5));
+
+			// Unknown anchor or value already in the list.
+			assert!(!List::insert_after(&0, 9, 6));
+			assert!(!List::insert_before(&0, 9, 6));
+			assert!(!List::insert_after(&0, 0, 3));
+			assert_eq!(List::len(&0), 6);
+		});
+	}
+
+	#[test]
+	fn remove_and_pop_front() {
+		with_externalities(&mut new_test_ext(), || {
+			List::append(&0, 1);
+			List::append(&0, 2);
+			List::append(&0, 3);
+
+			assert!(List::remove(&0, 2));
+			assert!(!List::remove(&0, 2));
+			assert_eq!(values(0), vec![1, 3]);
+			assert_eq!(List::len(&0), 2);
+
+			assert_eq!(List::pop_front(&0), Some(1));
+			assert_eq!(List::pop_front(&0), Some(3));
+			assert_eq!(List::pop_front(&0), None);
+			assert!(List::is_empty(&0));
+
+			// The list is usable again once emptied.
+			assert!(List::append(&0, 7));
+			assert_eq!(values(0), vec![7]);
+		});
+	}
+
+	#[test]
+	fn iteration_can_be_bounded_and_resumed() {
+		with_externalities(&mut new_test_ext(), || {
+			for value in 0..10 {
+				List::append(&0, value);
+			}
+
+			assert_eq!(List::iter(&0).take(3).collect::<Vec<_>>(), vec![0, 1, 2]);
+			assert_eq!(List::iter_from(&0, 8).collect::<Vec<_>>(), vec![8, 9]);
+			assert_eq!(List::iter_from(&0, 10).count(), 0);
+		});
+	}
+
+	#[test]
+	fn take_all_clears_list() {
+		with_externalities(&mut new_test_ext(), || {
+			List::append(&0, 1);
+			List::append(&0, 2);
+			List::append(&1, 3);
+
+			assert_eq!(List::take_all(&0), vec![1, 2]);
+			assert_eq!(Items::<Test>::get(&(0, None)), None);
+			assert_eq!(Items::<Test>::get(&(0, Some(1))), None);
+			assert_eq!(List::len(&0), 0);
+			assert_eq!(values(1), vec![3]);
+
+			List::clear(&1);
+			assert!(List::is_empty(&1));
+			assert_eq!(values(1), Vec::<u32>::new());
+		});
+	}
+}
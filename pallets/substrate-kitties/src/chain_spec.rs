@@ -1,7 +1,7 @@
 use primitives::{ed25519, sr25519, Pair};
 use substrate_kitties_runtime::{
 	AccountId, GenesisConfig, ConsensusConfig, TimestampConfig, BalancesConfig,
-	SudoConfig, IndicesConfig,
+	SudoConfig, IndicesConfig, KittiesConfig, Permill,
 };
 use substrate_service;
 
@@ -115,5 +115,11 @@ fn testnet_genesis(initial_authorities: Vec<AuthorityId>, endowed_accounts: Vec<
 		sudo: Some(SudoConfig {
 			key: root_key,
 		}),
+		kitties: Some(KittiesConfig {
+			mutation_rate: Permill::from_percent(1),
+			breeding_cooldown: 10,
+			mint_deposit: 1_000,
+			max_kitties_per_account: 100,
+		}),
 	}
 }
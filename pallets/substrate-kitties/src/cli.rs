@@ -1,13 +1,29 @@
-use crate::service;
+use crate::{service, rpc, index};
 use futures::{future, Future, sync::oneshot};
 use std::cell::RefCell;
 use tokio::runtime::Runtime;
 pub use substrate_cli::{VersionInfo, IntoExit, error};
-use substrate_cli::{informant, parse_and_execute, NoCustom};
+use substrate_cli::{informant, parse_and_execute, NoCustom, impl_augment_clap};
 use substrate_service::{ServiceFactory, Roles as ServiceRoles};
 use crate::chain_spec;
-use std::ops::Deref;
+use std::{ops::Deref, sync::Arc};
 use log::info;
+use structopt::StructOpt;
+
+/// Extra arguments of the `run` command.
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CustomArgs {
+	/// Serve the kitties RPC over HTTP on this port of the local interface.
+	#[structopt(long = "kitties-rpc-port", value_name = "PORT")]
+	kitties_rpc_port: Option<u16>,
+
+	/// Index kitty ownership, sales and floor prices in a local database, served by the kitties RPC.
+	/// Requires `--kitties-rpc-port`.
+	#[structopt(long = "kitties-index")]
+	kitties_index: bool,
+}
+
+impl_augment_clap!(CustomArgs);
 
 /// Parse command line arguments into service configuration.
 pub fn run<I, T, E>(args: I, exit: E, version: VersionInfo) -> error::Result<()> where
@@ -15,9 +31,9 @@ pub fn run<I, T, E>(args: I, exit: E, version: VersionInfo) -> error::Result<()>
 	T: Into<std::ffi::OsString> + Clone,
 	E: IntoExit,
 {
-	parse_and_execute::<service::Factory, NoCustom, NoCustom, _, _, _, _, _>(
+	parse_and_execute::<service::Factory, NoCustom, CustomArgs, _, _, _, _, _>(
 		load_spec, &version, "substrate-node", args, exit,
-	 	|exit, _custom_args, config| {
+	 	|exit, custom_args, config| {
 			info!("{}", version.name);
 			info!("  version {}", config.full_version());
 			info!("  by {}, 2017, 2018", version.author);
@@ -32,11 +48,31 @@ pub fn run<I, T, E>(args: I, exit: E, version: VersionInfo) -> error::Result<()>
 				 	service::Factory::new_light(config, executor).map_err(|e| format!("{:?}", e))?,
 					exit
 				),
-				_ => run_until_exit(
-					runtime,
-					service::Factory::new_full(config, executor).map_err(|e| format!("{:?}", e))?,
-					exit
-				),
+				_ => {
+					if custom_args.kitties_index && custom_args.kitties_rpc_port.is_none() {
+						return Err("--kitties-index is only served by the kitties RPC, set --kitties-rpc-port".into());
+					}
+					let kitties_index = if custom_args.kitties_index {
+						let path = index::path(&config.database_path);
+						info!("Kitties index at {}", path.display());
+						Some(Arc::new(index::Index::open(&path).map_err(|e| format!("{:?}", e))?))
+					} else {
+						None
+					};
+					let service = service::Factory::new_full(config, executor).map_err(|e| format!("{:?}", e))?;
+					if let Some(ref kitties_index) = kitties_index {
+						runtime.executor().spawn(index::start(kitties_index.clone(), service.client(), service.on_exit()));
+					}
+					let _kitties_rpc = match custom_args.kitties_rpc_port {
+						Some(port) => {
+							let addr = ([127, 0, 0, 1], port).into();
+							info!("Kitties RPC listening on {}", addr);
+							Some(rpc::start_http(&addr, service.client(), kitties_index).map_err(|e| format!("{:?}", e))?)
+						},
+						None => None,
+					};
+					run_until_exit(runtime, service, exit)
+				},
 			}.map_err(|e| format!("{:?}", e))
 		}
 	).map_err(Into::into).map(|_| ())
@@ -0,0 +1,625 @@
+//! Local index of kitty events, built from the blocks the node imports.
+//!
+//! Every block that becomes the best block has its `System Events` decoded and the kitty events
+//! applied to a RocksDB database next to the chain database. Each indexed block keeps an undo log of
+//! the entries it changed. When the best chain is reorganised, the retracted blocks are undone and
+//! the enacted ones are indexed instead. Blocks more than `UNDO_DEPTH` below the best block lose
+//! their undo log and can no longer be retracted.
+//!
+//! The events are read from the state of each block. Indexing blocks from before the index was
+//! enabled therefore needs their state, so run the node with `--pruning archive` to index from genesis.
+
+use std::{collections::HashMap, io, path::{Path, PathBuf}, sync::Arc};
+use futures::{Future, Stream};
+use kvdb::{DBTransaction, KeyValueDB};
+use kvdb_rocksdb::{Database, DatabaseConfig};
+use log::{info, warn};
+use parity_codec::{Decode, Encode};
+use parking_lot::Mutex;
+use primitives::{Blake2Hasher, storage::StorageKey};
+use serde::{Deserialize, Serialize};
+use substrate_client::{self as client, Client, BlockchainEvents};
+use substrate_kitties_runtime::{
+	AccountId, Balance, BlockNumber, Event, EventRecord, Hash, KittyIndex,
+	kitties::RawEvent,
+	opaque::{Block, BlockId, Header},
+};
+
+/// Blocks below the best block that can still be retracted by a reorganisation.
+const UNDO_DEPTH: BlockNumber = 256;
+
+/// Best indexed block number and current floor price.
+const COL_META: Option<u32> = Some(0);
+/// Indexed blocks by number.
+const COL_BLOCKS: Option<u32> = Some(1);
+/// Owners of a kitty, by kitty id, block number and event index.
+const COL_OWNERS: Option<u32> = Some(2);
+/// Sales of a kitty, by kitty id, block number and event index.
+const COL_SALES: Option<u32> = Some(3);
+/// Asking price of the kitties for sale, by kitty id.
+const COL_ASKS: Option<u32> = Some(4);
+/// Floor price after each block that changed it, by block number.
+const COL_FLOOR: Option<u32> = Some(5);
+/// The kitties for sale, by asking price and kitty id.
+const COL_ASK_PRICES: Option<u32> = Some(6);
+const NUM_COLUMNS: u32 = 7;
+
+const KEY_BEST: &[u8] = b"best";
+const KEY_FLOOR: &[u8] = b"floor";
+
+/// A change of owner of a kitty.
+#[derive(Encode, Decode, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnershipRecord {
+	pub block: BlockNumber,
+	/// `None` once the kitty is burned.
+	pub owner: Option<AccountId>,
+}
+
+/// How a kitty is sold.
+#[derive(Encode, Decode, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum SaleKind {
+	/// Bought at its asking price.
+	Ask,
+	Auction,
+	/// The owner accepted an offer.
+	Offer,
+}
+
+/// A sale of a kitty.
+#[derive(Encode, Decode, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SaleRecord {
+	pub block: BlockNumber,
+	pub kind: SaleKind,
+	pub seller: AccountId,
+	pub buyer: AccountId,
+	pub price: Balance,
+}
+
+/// The lowest asking price from a block on, `None` when no kitty is for sale.
+#[derive(Encode, Decode, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FloorPrice {
+	pub block: BlockNumber,
+	pub price: Option<Balance>,
+}
+
+/// An indexed block and the previous value of every entry it changed.
+#[derive(Encode, Decode)]
+struct IndexedBlock {
+	hash: Hash,
+	undo: Vec<(u32, Vec<u8>, Option<Vec<u8>>)>,
+}
+
+/// Path of the index database of a chain database.
+pub fn path(database_path: &str) -> PathBuf {
+	let database_path = Path::new(database_path);
+	database_path.parent().unwrap_or(database_path).join("kitties-index")
+}
+
+fn number_key(number: BlockNumber) -> [u8; 8] {
+	number.to_be_bytes()
+}
+
+fn key_number(key: &[u8]) -> BlockNumber {
+	let mut bytes = [0u8; 8];
+	bytes.copy_from_slice(&key[..8]);
+	BlockNumber::from_be_bytes(bytes)
+}
+
+/// Keys of a kitty's records sort by block and then by event, so its history reads in order.
+fn record_key(kitty_id: KittyIndex, number: BlockNumber, event_index: u32) -> Vec<u8> {
+	let mut key = kitty_id.to_be_bytes().to_vec();
+	key.extend_from_slice(&number.to_be_bytes());
+	key.extend_from_slice(&event_index.to_be_bytes());
+	key
+}
+
+/// Keys of the asks sort by price, so the first one is the floor.
+fn price_key(price: Balance, kitty_id: KittyIndex) -> Vec<u8> {
+	let mut key = price.to_be_bytes().to_vec();
+	key.extend_from_slice(&kitty_id.to_be_bytes());
+	key
+}
+
+fn key_price(key: &[u8]) -> Balance {
+	let mut bytes = [0u8; 16];
+	bytes.copy_from_slice(&key[..16]);
+	Balance::from_be_bytes(bytes)
+}
+
+fn decode<T: Decode>(value: &[u8]) -> io::Result<T> {
+	T::decode(&mut &value[..]).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Corrupted kitties index"))
+}
+
+fn client_error<E: std::fmt::Debug>(err: E) -> io::Error {
+	io::Error::new(io::ErrorKind::Other, format!("{:?}", err))
+}
+
+/// The writes of a block, with the previous values they overwrite.
+struct Batch<'a> {
+	db: &'a Database,
+	transaction: DBTransaction,
+	overlay: HashMap<(u32, Vec<u8>), Option<Vec<u8>>>,
+	undo: Vec<(u32, Vec<u8>, Option<Vec<u8>>)>,
+}
+
+impl<'a> Batch<'a> {
+	fn new(db: &'a Database) -> Self {
+		Batch { db, transaction: db.transaction(), overlay: HashMap::new(), undo: Vec::new() }
+	}
+
+	fn get(&self, col: Option<u32>, key: &[u8]) -> io::Result<Option<Vec<u8>>> {
+		match self.overlay.get(&(col.unwrap_or_default(), key.to_vec())) {
+			Some(value) => Ok(value.clone()),
+			None => Ok(self.db.get(col, key)?.map(|value| value.to_vec())),
+		}
+	}
+
+	fn set(&mut self, col: Option<u32>, key: Vec<u8>, value: Option<Vec<u8>>) -> io::Result<()> {
+		let column = col.unwrap_or_default();
+		// Undoing restores the value from before the block, later writes to the key need no entry.
+		if !self.overlay.contains_key(&(column, key.clone())) {
+			let previous = self.get(col, &key)?;
+			self.undo.push((column, key.clone(), previous));
+		}
+		match value {
+			Some(ref value) => self.transaction.put(col, &key, value),
+			None => self.transaction.delete(col, &key),
+		}
+		self.overlay.insert((column, key), value);
+		Ok(())
+	}
+
+	/// Sets the asking price of a kitty, `None` when it is no longer for sale.
+	fn set_ask(&mut self, kitty_id: KittyIndex, price: Option<Balance>) -> io::Result<()> {
+		let key = kitty_id.to_be_bytes().to_vec();
+		if let Some(previous) = self.get(COL_ASKS, &key)? {
+			self.set(COL_ASK_PRICES, price_key(decode(&previous)?, kitty_id), None)?;
+		}
+		if let Some(price) = price {
+			self.set(COL_ASK_PRICES, price_key(price, kitty_id), Some(Vec::new()))?;
+		}
+		self.set(COL_ASKS, key, price.map(|price| price.encode()))
+	}
+
+	/// The lowest asking price including the asks changed by this batch.
+	fn floor(&self) -> io::Result<Option<Balance>> {
+		let column = COL_ASK_PRICES.unwrap_or_default();
+		// Skips the asks this batch removed, at most as many as its events.
+		let stored = KeyValueDB::iter(self.db, COL_ASK_PRICES)
+			.map(|(key, _)| key.to_vec())
+			.find(|key| self.overlay.get(&(column, key.clone())) != Some(&None));
+		let added = self.overlay.iter()
+			.filter(|((col, _), value)| *col == column && value.is_some())
+			.map(|((_, key), _)| key)
+			.min();
+		let first = match (stored, added) {
+			(Some(stored), Some(added)) => Some(stored.min(added.clone())),
+			(stored, added) => stored.or_else(|| added.cloned()),
+		};
+		Ok(first.map(|key| key_price(&key)))
+	}
+}
+
+/// Kitty events indexed by kitty and block.
+pub struct Index {
+	db: Database,
+	/// Serialises indexing, queries only read.
+	import_lock: Mutex<()>,
+}
+
+impl Index {
+	/// Opens the index database at `path`, creating it if needed.
+	pub fn open(path: &Path) -> io::Result<Self> {
+		let config = DatabaseConfig::with_columns(Some(NUM_COLUMNS));
+		let path = path.to_str().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Invalid index path"))?;
+		Ok(Index { db: Database::open(&config, path)?, import_lock: Mutex::new(()) })
+	}
+
+	/// Number of the best indexed block, `None` before the first block is indexed.
+	pub fn best_number(&self) -> io::Result<Option<BlockNumber>> {
+		self.db.get(COL_META, KEY_BEST)?.map(|value| decode(&value)).transpose()
+	}
+
+	fn indexed_hash(&self, number: BlockNumber) -> io::Result<Option<Hash>> {
+		self.db.get(COL_BLOCKS, &number_key(number))?
+			.map(|value| decode::<IndexedBlock>(&value).map(|block| block.hash))
+			.transpose()
+	}
+
+	/// Owners of a kitty from its creation on, oldest first.
+	pub fn ownership_history(&self, kitty_id: KittyIndex) -> io::Result<Vec<OwnershipRecord>> {
+		self.history(COL_OWNERS, kitty_id)
+	}
+
+	/// Sales of a kitty, oldest first.
+	pub fn sale_history(&self, kitty_id: KittyIndex) -> io::Result<Vec<SaleRecord>> {
+		self.history(COL_SALES, kitty_id)
+	}
+
+	fn history<T: Decode>(&self, col: Option<u32>, kitty_id: KittyIndex) -> io::Result<Vec<T>> {
+		let prefix = kitty_id.to_be_bytes();
+		// The inherent `Database` iterators return `None` for a missing column, the trait ones are empty.
+		KeyValueDB::iter_from_prefix(&self.db, col, &prefix)
+			.take_while(|(key, _)| key.starts_with(&prefix))
+			.map(|(_, value)| decode(&value))
+			.collect()
+	}
+
+	/// The lowest asking price at the best indexed block.
+	pub fn floor_price(&self) -> io::Result<Option<Balance>> {
+		Ok(self.db.get(COL_META, KEY_FLOOR)?.map(|value| decode(&value)).transpose()?.and_then(|floor| floor))
+	}
+
+	/// Changes of the floor price in the blocks `from..=to`, oldest first.
+	pub fn floor_price_history(&self, from: BlockNumber, to: BlockNumber) -> io::Result<Vec<FloorPrice>> {
+		let (from, to) = (number_key(from), number_key(to));
+		KeyValueDB::iter(&self.db, COL_FLOOR)
+			.skip_while(|(key, _)| key[..] < from[..])
+			.take_while(|(key, _)| key[..] <= to[..])
+			.map(|(key, value)| Ok(FloorPrice {
+				block: key_number(&key),
+				price: decode(&value)?,
+			}))
+			.collect()
+	}
+
+	/// Indexes a new best block and any of its ancestors that are not indexed yet. Indexed blocks
+	/// that are not ancestors of it are undone first.
+	pub fn import_best<B, E, RA>(&self, client: &Client<B, E, Block, RA>, hash: Hash, header: &Header) -> io::Result<()> where
+		B: client::backend::Backend<Block, Blake2Hasher>,
+		E: client::CallExecutor<Block, Blake2Hasher>,
+	{
+		self.import(
+			header.number,
+			hash,
+			header.parent_hash,
+			|hash| Ok(client.header(&BlockId::hash(hash)).map_err(client_error)?
+				.ok_or_else(|| client_error("Missing header of an ancestor of the best block"))?
+				.parent_hash),
+			|hash| events(client, hash),
+		)
+	}
+
+	/// Indexes the block `number` with the hash `hash` as the new best block, looking up the parents
+	/// of its ancestors with `parent_of` and the events of the blocks to index with `events_of`.
+	fn import<P, V>(
+		&self,
+		number: BlockNumber,
+		hash: Hash,
+		parent_hash: Hash,
+		mut parent_of: P,
+		mut events_of: V,
+	) -> io::Result<()> where
+		P: FnMut(Hash) -> io::Result<Hash>,
+		V: FnMut(Hash) -> io::Result<Vec<Event>>,
+	{
+		let _lock = self.import_lock.lock();
+		let best = self.best_number()?;
+		let new_best = number;
+
+		// Walk back to the last block the index agrees with.
+		let mut enacted = vec![(number, hash)];
+		let (mut number, mut parent) = (number, parent_hash);
+		while number > 0 {
+			number -= 1;
+			let indexed = match best {
+				Some(best) if number <= best => self.indexed_hash(number)?,
+				_ => None,
+			};
+			match indexed {
+				Some(indexed) if indexed == parent => break,
+				// Below the best block but past the undo depth, or from before the index.
+				None if best.map_or(false, |best| number <= best) => break,
+				_ => (),
+			}
+			enacted.push((number, parent));
+			parent = parent_of(parent)?;
+		}
+
+		let first = enacted.last().map(|(number, _)| *number).unwrap_or(new_best);
+		if let Some(best) = best {
+			for number in (first..=best).rev() {
+				self.revert(number)?;
+			}
+		}
+		for (number, hash) in enacted.into_iter().rev() {
+			let events = match events_of(hash) {
+				Ok(events) => events,
+				Err(err) => {
+					warn!("Kitties index: no events for block #{} ({:?}), its state is unavailable", number, err);
+					Vec::new()
+				},
+			};
+			self.apply(number, hash, events)?;
+		}
+		Ok(())
+	}
+
+	/// Undoes the indexed block `number`, if any.
+	fn revert(&self, number: BlockNumber) -> io::Result<()> {
+		let block: IndexedBlock = match self.db.get(COL_BLOCKS, &number_key(number))? {
+			Some(value) => decode(&value)?,
+			None => return Ok(()),
+		};
+		let mut transaction = self.db.transaction();
+		for (column, key, previous) in block.undo.into_iter().rev() {
+			match previous {
+				Some(value) => transaction.put(Some(column), &key, &value),
+				None => transaction.delete(Some(column), &key),
+			}
+		}
+		transaction.delete(COL_BLOCKS, &number_key(number));
+		match number.checked_sub(1) {
+			Some(parent) => transaction.put(COL_META, KEY_BEST, &parent.encode()),
+			None => transaction.delete(COL_META, KEY_BEST),
+		}
+		self.db.write(transaction)
+	}
+
+	fn apply(&self, number: BlockNumber, hash: Hash, events: Vec<Event>) -> io::Result<()> {
+		let mut batch = Batch::new(&self.db);
+		let mut asks_changed = false;
+
+		for (index, event) in events.into_iter().enumerate() {
+			let event = match event {
+				Event::kitties(event) => event,
+				_ => continue,
+			};
+			let index = index as u32;
+			let owner = |batch: &mut Batch, kitty_id, owner| batch.set(
+				COL_OWNERS,
+				record_key(kitty_id, number, index),
+				Some(OwnershipRecord { block: number, owner }.encode()),
+			);
+			let sale = |batch: &mut Batch, kitty_id, kind, seller, buyer, price| batch.set(
+				COL_SALES,
+				record_key(kitty_id, number, index),
+				Some(SaleRecord { block: number, kind, seller, buyer, price }.encode()),
+			);
+
+			// Mirrors when the runtime sets and clears `KittyPrices`.
+			match event {
+				RawEvent::Created(to, kitty_id) | RawEvent::Transferred(_, to, kitty_id) => {
+					owner(&mut batch, kitty_id, Some(to))?;
+				},
+				RawEvent::Gifted(_, to, kitty_id, _) => {
+					owner(&mut batch, kitty_id, Some(to))?;
+					batch.set_ask(kitty_id, None)?;
+					asks_changed = true;
+				},
+				RawEvent::Burned(_, kitty_id) => {
+					owner(&mut batch, kitty_id, None)?;
+					batch.set_ask(kitty_id, None)?;
+					asks_changed = true;
+				},
+				RawEvent::Ask(_, kitty_id, price) => {
+					batch.set_ask(kitty_id, price)?;
+					asks_changed = true;
+				},
+				RawEvent::AuctionCreated(_, kitty_id, _, _) => {
+					batch.set_ask(kitty_id, None)?;
+					asks_changed = true;
+				},
+				RawEvent::Sold(from, to, kitty_id, price) => {
+					owner(&mut batch, kitty_id, Some(to.clone()))?;
+					sale(&mut batch, kitty_id, SaleKind::Ask, from, to, price)?;
+					batch.set_ask(kitty_id, None)?;
+					asks_changed = true;
+				},
+				RawEvent::OfferAccepted(from, to, kitty_id, price) => {
+					owner(&mut batch, kitty_id, Some(to.clone()))?;
+					sale(&mut batch, kitty_id, SaleKind::Offer, from, to, price)?;
+					batch.set_ask(kitty_id, None)?;
+					asks_changed = true;
+				},
+				RawEvent::AuctionSettled(from, to, kitty_id, price) => {
+					owner(&mut batch, kitty_id, Some(to.clone()))?;
+					sale(&mut batch, kitty_id, SaleKind::Auction, from, to, price)?;
+				},
+				_ => (),
+			}
+		}
+
+		if asks_changed {
+			let floor = batch.floor()?;
+			let previous: Option<Option<Balance>> = batch.get(COL_META, KEY_FLOOR)?.map(|value| decode(&value)).transpose()?;
+			if previous.unwrap_or(None) != floor {
+				batch.set(COL_META, KEY_FLOOR.to_vec(), Some(floor.encode()))?;
+				batch.set(COL_FLOOR, number_key(number).to_vec(), Some(floor.encode()))?;
+			}
+		}
+
+		let Batch { mut transaction, undo, .. } = batch;
+		transaction.put(COL_BLOCKS, &number_key(number), &IndexedBlock { hash, undo }.encode());
+		transaction.put(COL_META, KEY_BEST, &number.encode());
+		if let Some(pruned) = number.checked_sub(UNDO_DEPTH) {
+			transaction.delete(COL_BLOCKS, &number_key(pruned));
+		}
+		self.db.write(transaction)
+	}
+}
+
+/// The events deposited in a block.
+fn events<B, E, RA>(client: &Client<B, E, Block, RA>, hash: Hash) -> io::Result<Vec<Event>> where
+	B: client::backend::Backend<Block, Blake2Hasher>,
+	E: client::CallExecutor<Block, Blake2Hasher>,
+{
+	let key = StorageKey(sr_io::twox_128(b"System Events").to_vec());
+	match client.storage(&BlockId::hash(hash), &key).map_err(client_error)? {
+		Some(data) => Ok(decode::<Vec<EventRecord<Event>>>(&data.0)?.into_iter().map(|record| record.event).collect()),
+		None => Ok(Vec::new()),
+	}
+}
+
+/// Indexes the current best chain and then every new best block until `exit` fires.
+pub fn start<B, E, RA, X>(
+	index: Arc<Index>,
+	client: Arc<Client<B, E, Block, RA>>,
+	exit: X,
+) -> impl Future<Item = (), Error = ()> where
+	B: client::backend::Backend<Block, Blake2Hasher> + Send + Sync + 'static,
+	E: client::CallExecutor<Block, Blake2Hasher> + Send + Sync + 'static,
+	RA: Send + Sync + 'static,
+	X: Future<Item = (), Error = ()>,
+{
+	// Subscribe first, so blocks imported while catching up are indexed after it.
+	let notifications = client.import_notification_stream();
+
+	futures::future::lazy(move || {
+		let catch_up = client.info().map_err(client_error)
+			.and_then(|info| {
+				let hash = info.chain.best_hash;
+				let header = client.header(&BlockId::hash(hash)).map_err(client_error)?
+					.ok_or_else(|| client_error("Missing header of the best block"))?;
+				index.import_best(&*client, hash, &header)
+			});
+		match catch_up {
+			Ok(()) => info!("Kitties index up to date at block #{}", index.best_number().ok().and_then(|n| n).unwrap_or(0)),
+			Err(err) => warn!("Kitties index failed to catch up: {:?}", err),
+		}
+
+		notifications
+			.filter(|notification| notification.is_new_best)
+			.for_each(move |notification| {
+				if let Err(err) = index.import_best(&*client, notification.hash, &notification.header) {
+					warn!("Kitties index failed to index block {}: {:?}", notification.hash, err);
+				}
+				Ok(())
+			})
+	})
+		.select(exit)
+		.then(|_| Ok(()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn account(seed: u8) -> AccountId {
+		AccountId::from_raw([seed; 32])
+	}
+
+	fn block_hash(seed: u8) -> Hash {
+		Hash::from([seed; 32])
+	}
+
+	fn ask(kitty_id: KittyIndex, price: Option<Balance>) -> Event {
+		Event::kitties(RawEvent::Ask(account(1), kitty_id, price))
+	}
+
+	fn new_index() -> (tempfile::TempDir, Index) {
+		let dir = tempfile::Builder::new().prefix("kitties-index").tempdir().unwrap();
+		let index = Index::open(dir.path()).unwrap();
+		(dir, index)
+	}
+
+	/// Blocks by hash, with their number, parent and events.
+	type Chain = HashMap<Hash, (BlockNumber, Hash, Vec<Event>)>;
+
+	fn import(index: &Index, chain: &Chain, hash: Hash) {
+		let (number, parent, _) = chain[&hash].clone();
+		index.import(
+			number,
+			hash,
+			parent,
+			|hash| Ok(chain[&hash].1),
+			|hash| Ok(chain[&hash].2.clone()),
+		).unwrap();
+	}
+
+	/// Whether nothing but the best block number is stored.
+	fn is_empty(index: &Index) -> bool {
+		index.floor_price().unwrap().is_none() && (1..NUM_COLUMNS).all(|column| KeyValueDB::iter(&index.db, Some(column)).next().is_none())
+	}
+
+	#[test]
+	fn revert_undoes_apply() {
+		let (_dir, index) = new_index();
+
+		index.apply(1, block_hash(1), vec![
+			Event::kitties(RawEvent::Created(account(1), 0)),
+			ask(0, Some(50)),
+		]).unwrap();
+		index.apply(2, block_hash(2), vec![
+			Event::kitties(RawEvent::Sold(account(1), account(2), 0, 50)),
+		]).unwrap();
+		assert_eq!(index.best_number().unwrap(), Some(2));
+		assert_eq!(index.floor_price().unwrap(), None);
+		assert_eq!(index.ownership_history(0).unwrap(), vec![
+			OwnershipRecord { block: 1, owner: Some(account(1)) },
+			OwnershipRecord { block: 2, owner: Some(account(2)) },
+		]);
+		assert_eq!(index.sale_history(0).unwrap(), vec![
+			SaleRecord { block: 2, kind: SaleKind::Ask, seller: account(1), buyer: account(2), price: 50 },
+		]);
+
+		index.revert(2).unwrap();
+		assert_eq!(index.best_number().unwrap(), Some(1));
+		assert_eq!(index.floor_price().unwrap(), Some(50));
+		assert_eq!(index.ownership_history(0).unwrap(), vec![
+			OwnershipRecord { block: 1, owner: Some(account(1)) },
+		]);
+		assert_eq!(index.sale_history(0).unwrap(), vec![]);
+		assert_eq!(index.floor_price_history(0, 2).unwrap(), vec![
+			FloorPrice { block: 1, price: Some(50) },
+		]);
+
+		index.revert(1).unwrap();
+		assert_eq!(index.best_number().unwrap(), Some(0));
+		assert!(is_empty(&index));
+	}
+
+	#[test]
+	fn new_best_fork_replaces_retracted_blocks() {
+		let (_dir, index) = new_index();
+		let mut chain = Chain::new();
+		chain.insert(block_hash(0), (0, Hash::default(), vec![]));
+		chain.insert(block_hash(1), (1, block_hash(0), vec![
+			Event::kitties(RawEvent::Created(account(1), 0)),
+			ask(0, Some(30)),
+		]));
+		chain.insert(block_hash(2), (2, block_hash(1), vec![ask(0, Some(20))]));
+		// A fork from block 1 that becomes longer.
+		chain.insert(block_hash(12), (2, block_hash(1), vec![ask(0, Some(40))]));
+		chain.insert(block_hash(13), (3, block_hash(12), vec![]));
+
+		import(&index, &chain, block_hash(2));
+		assert_eq!(index.floor_price().unwrap(), Some(20));
+
+		import(&index, &chain, block_hash(13));
+		assert_eq!(index.best_number().unwrap(), Some(3));
+		assert_eq!(index.indexed_hash(2).unwrap(), Some(block_hash(12)));
+		assert_eq!(index.floor_price().unwrap(), Some(40));
+		assert_eq!(index.floor_price_history(0, 3).unwrap(), vec![
+			FloorPrice { block: 1, price: Some(30) },
+			FloorPrice { block: 2, price: Some(40) },
+		]);
+	}
+
+	#[test]
+	fn floor_price_is_restored_by_revert() {
+		let (_dir, index) = new_index();
+
+		index.apply(1, block_hash(1), vec![ask(0, Some(30)), ask(1, Some(10)), ask(2, Some(20))]).unwrap();
+		assert_eq!(index.floor_price().unwrap(), Some(10));
+		// The floor reads the asks changed earlier in the same block.
+		index.apply(2, block_hash(2), vec![ask(1, None), ask(2, Some(25)), ask(2, Some(15))]).unwrap();
+		assert_eq!(index.floor_price().unwrap(), Some(15));
+		index.apply(3, block_hash(3), vec![ask(2, None)]).unwrap();
+		assert_eq!(index.floor_price().unwrap(), Some(30));
+
+		index.revert(3).unwrap();
+		assert_eq!(index.floor_price().unwrap(), Some(15));
+		index.revert(2).unwrap();
+		assert_eq!(index.floor_price().unwrap(), Some(10));
+		index.apply(2, block_hash(22), vec![ask(0, None)]).unwrap();
+		assert_eq!(index.floor_price().unwrap(), Some(10));
+		assert_eq!(index.floor_price_history(0, 3).unwrap(), vec![
+			FloorPrice { block: 1, price: Some(10) },
+		]);
+	}
+}
@@ -0,0 +1,182 @@
+//! Kitties RPC, served on its own HTTP endpoint next to the node's RPC.
+
+use std::{net::SocketAddr, sync::Arc};
+use jsonrpc_core::{Error, ErrorCode, IoHandler, Result};
+use jsonrpc_derive::rpc;
+use jsonrpc_http_server::{Server, ServerBuilder};
+use primitives::Blake2Hasher;
+use substrate_client::{self as client, Client, runtime_api::ProvideRuntimeApi};
+use crate::index::{Index, OwnershipRecord, SaleRecord, FloorPrice};
+use substrate_kitties_runtime::{
+	AccountId, Balance, BlockNumber, KittyIndex,
+	kitties::{KittiesApi, KittiesPage},
+	opaque::{Block, BlockId},
+	Hash,
+};
+
+/// Kitties RPC methods.
+#[rpc]
+pub trait KittiesRpc {
+	/// Number of kitties ever created.
+	#[rpc(name = "kitties_kittiesCount")]
+	fn kitties_count(&self, at: Option<Hash>) -> Result<KittyIndex>;
+
+	/// Number of kitties owned by an account.
+	#[rpc(name = "kitties_ownedKittiesCount")]
+	fn owned_kitties_count(&self, account: AccountId, at: Option<Hash>) -> Result<u64>;
+
+	/// A page of the kitties owned by an account. Pass the `next` cursor of a page to get the following one.
+	#[rpc(name = "kitties_ownedKitties")]
+	fn owned_kitties(
+		&self,
+		account: AccountId,
+		cursor: Option<KittyIndex>,
+		limit: u32,
+		at: Option<Hash>,
+	) -> Result<KittiesPage<AccountId, KittyIndex, Balance>>;
+}
+
+/// Kitties RPC methods backed by the local index of kitty events, see `--kitties-index`.
+#[rpc]
+pub trait KittiesIndexRpc {
+	/// Owners of a kitty from its creation on, oldest first. The owner is `null` once it is burned.
+	#[rpc(name = "kitties_ownershipHistory")]
+	fn ownership_history(&self, kitty_id: KittyIndex) -> Result<Vec<OwnershipRecord>>;
+
+	/// Sales of a kitty at its asking price, in auctions and through offers, oldest first.
+	#[rpc(name = "kitties_saleHistory")]
+	fn sale_history(&self, kitty_id: KittyIndex) -> Result<Vec<SaleRecord>>;
+
+	/// The lowest asking price of the kitties for sale at the best indexed block.
+	#[rpc(name = "kitties_floorPrice")]
+	fn floor_price(&self) -> Result<Option<Balance>>;
+
+	/// Changes of the floor price from block `from` up to block `to`, to the best indexed block by default.
+	#[rpc(name = "kitties_floorPriceHistory")]
+	fn floor_price_history(&self, from: BlockNumber, to: Option<BlockNumber>) -> Result<Vec<FloorPrice>>;
+}
+
+/// Kitties RPC methods backed by the runtime API.
+pub struct Kitties<B, E, RA> {
+	client: Arc<Client<B, E, Block, RA>>,
+}
+
+impl<B, E, RA> Kitties<B, E, RA> {
+	/// Kitties RPC over the given client.
+	pub fn new(client: Arc<Client<B, E, Block, RA>>) -> Self {
+		Kitties { client }
+	}
+}
+
+fn client_error<E: std::fmt::Debug>(err: E) -> Error {
+	Error {
+		code: ErrorCode::ServerError(1),
+		message: "Unable to query kitties".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+/// Kitties index RPC methods.
+pub struct KittiesIndex {
+	index: Arc<Index>,
+}
+
+impl KittiesIndex {
+	/// Kitties index RPC over the given index.
+	pub fn new(index: Arc<Index>) -> Self {
+		KittiesIndex { index }
+	}
+}
+
+fn index_error(err: std::io::Error) -> Error {
+	Error {
+		code: ErrorCode::ServerError(2),
+		message: "Unable to query the kitties index".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+impl KittiesIndexRpc for KittiesIndex {
+	fn ownership_history(&self, kitty_id: KittyIndex) -> Result<Vec<OwnershipRecord>> {
+		self.index.ownership_history(kitty_id).map_err(index_error)
+	}
+
+	fn sale_history(&self, kitty_id: KittyIndex) -> Result<Vec<SaleRecord>> {
+		self.index.sale_history(kitty_id).map_err(index_error)
+	}
+
+	fn floor_price(&self) -> Result<Option<Balance>> {
+		self.index.floor_price().map_err(index_error)
+	}
+
+	fn floor_price_history(&self, from: BlockNumber, to: Option<BlockNumber>) -> Result<Vec<FloorPrice>> {
+		self.index.floor_price_history(from, to.unwrap_or(BlockNumber::max_value())).map_err(index_error)
+	}
+}
+
+impl<B, E, RA> Kitties<B, E, RA> where
+	B: client::backend::Backend<Block, Blake2Hasher> + Send + Sync + 'static,
+	E: client::CallExecutor<Block, Blake2Hasher> + Send + Sync + 'static,
+	RA: Send + Sync + 'static,
+{
+	fn block_id(&self, at: Option<Hash>) -> Result<BlockId> {
+		let hash = match at {
+			Some(hash) => hash,
+			None => self.client.info().map_err(client_error)?.chain.best_hash,
+		};
+		Ok(BlockId::hash(hash))
+	}
+}
+
+impl<B, E, RA> KittiesRpc for Kitties<B, E, RA> where
+	B: client::backend::Backend<Block, Blake2Hasher> + Send + Sync + 'static,
+	E: client::CallExecutor<Block, Blake2Hasher> + Send + Sync + 'static,
+	RA: Send + Sync + 'static,
+	Client<B, E, Block, RA>: ProvideRuntimeApi,
+	<Client<B, E, Block, RA> as ProvideRuntimeApi>::Api: KittiesApi<Block, AccountId, KittyIndex, Balance>,
+{
+	fn kitties_count(&self, at: Option<Hash>) -> Result<KittyIndex> {
+		let at = self.block_id(at)?;
+		self.client.runtime_api().kitties_count(&at).map_err(client_error)
+	}
+
+	fn owned_kitties_count(&self, account: AccountId, at: Option<Hash>) -> Result<u64> {
+		let at = self.block_id(at)?;
+		self.client.runtime_api().owned_kitties_count(&at, account).map_err(client_error)
+	}
+
+	fn owned_kitties(
+		&self,
+		account: AccountId,
+		cursor: Option<KittyIndex>,
+		limit: u32,
+		at: Option<Hash>,
+	) -> Result<KittiesPage<AccountId, KittyIndex, Balance>> {
+		let at = self.block_id(at)?;
+		self.client.runtime_api().owned_kitties(&at, account, cursor, limit).map_err(client_error)
+	}
+}
+
+/// Starts the kitties RPC HTTP server, it stops when the returned server is dropped. The index
+/// methods are only served when the node keeps an index.
+pub fn start_http<B, E, RA>(
+	addr: &SocketAddr,
+	client: Arc<Client<B, E, Block, RA>>,
+	index: Option<Arc<Index>>,
+) -> std::io::Result<Server> where
+	B: client::backend::Backend<Block, Blake2Hasher> + Send + Sync + 'static,
+	E: client::CallExecutor<Block, Blake2Hasher> + Send + Sync + 'static,
+	RA: Send + Sync + 'static,
+	Client<B, E, Block, RA>: ProvideRuntimeApi,
+	<Client<B, E, Block, RA> as ProvideRuntimeApi>::Api: KittiesApi<Block, AccountId, KittyIndex, Balance>,
+{
+	let mut io = IoHandler::default();
+	io.extend_with(Kitties::new(client).to_delegate());
+	if let Some(index) = index {
+		io.extend_with(KittiesIndex::new(index).to_delegate());
+	}
+
+	ServerBuilder::new(io)
+		.threads(1)
+		.start_http(addr)
+}
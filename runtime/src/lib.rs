@@ -9,7 +9,7 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 use sp_std::prelude::*;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
-	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature,
+	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature, ModuleId,
 	transaction_validity::{TransactionValidity, TransactionSource},
 };
 use sp_runtime::traits::{
@@ -65,6 +65,9 @@ pub type Index = u32;
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
+/// Index of a kitty.
+pub type KittyIndex = u32;
+
 /// Digest item type.
 pub type DigestItem = generic::DigestItem<Hash>;
 
@@ -255,9 +258,64 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
-/// Configure the pallet template in pallets/template.
+parameter_types! {
+	pub const MaxClaimOwners: u32 = 16;
+	pub const MaxProofLength: u8 = 128;
+	pub const DigestClaimsOnly: bool = false;
+	pub const MaxClaimDescriptionLength: u32 = 256;
+	pub const MaxMimeTypeLength: u32 = 64;
+	pub const LapseCleanupLimit: u32 = 100;
+	pub const ClaimDepositPerByte: Balance = 10;
+}
+
+/// Configure the proof of existence pallet in pallets/poe.
 impl template::Config for Runtime {
 	type Event = Event;
+	type MaxOwners = MaxClaimOwners;
+	type MaxProofLength = MaxProofLength;
+	type DigestClaimsOnly = DigestClaimsOnly;
+	type MaxDescriptionLength = MaxClaimDescriptionLength;
+	type MaxMimeTypeLength = MaxMimeTypeLength;
+	type LapseCleanupLimit = LapseCleanupLimit;
+	type Currency = Balances;
+	type DepositPerByte = ClaimDepositPerByte;
+	type WeightInfo = template::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const MaxOffersPerKitty: u32 = 16;
+	pub const MaxMemoLength: u32 = 140;
+	pub const MaxBatchSize: u32 = 32;
+	pub const MaxRoyalty: Permill = Permill::from_percent(10);
+	pub const KittiesModuleId: ModuleId = ModuleId(*b"py/kitty");
+	pub const MaxNameLength: u32 = 32;
+	pub const MaxDescriptionLength: u32 = 256;
+	pub const MetadataDepositPerByte: Balance = 10;
+	pub const BreedCommitDeposit: Balance = 1_000;
+	pub const RevealPeriod: BlockNumber = 10;
+	pub const MaxScheduledPerBlock: u32 = 64;
+	pub const CollectionDeposit: Balance = 10_000;
+}
+
+impl pallet_kitties::Config for Runtime {
+	type Event = Event;
+	type KittyIndex = KittyIndex;
+	type CollectionId = u32;
+	type CollectionDeposit = CollectionDeposit;
+	type Currency = Balances;
+	type Randomness = RandomnessCollectiveFlip;
+	type MaxOffersPerKitty = MaxOffersPerKitty;
+	type MaxMemoLength = MaxMemoLength;
+	type MaxBatchSize = MaxBatchSize;
+	type MaxRoyalty = MaxRoyalty;
+	type ModuleId = KittiesModuleId;
+	type MaxNameLength = MaxNameLength;
+	type MaxDescriptionLength = MaxDescriptionLength;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type BreedCommitDeposit = BreedCommitDeposit;
+	type RevealPeriod = RevealPeriod;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type WeightInfo = pallet_kitties::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -277,39 +335,10 @@ construct_runtime!(
 		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
 		// Include the custom logic from the template pallet in the runtime.
 		TemplateModule: template::{Module, Call, Storage, Event<T>},
+		Kitties: pallet_kitties::{Module, Call, Storage, Event<T>, Config<T>},
 	}
 );
 
-/// 对poe配置接口进行实现
-impl poe::Trait for Runtime{
-	type Event = Event;
-}
-///作业
-construct_runtime!(
-	pub enum Runtime where
-		Block = Block,
-		NodeBlock = opaque::Block,
-		UncheckedExtrinsic = UncheckedExtrinsic
-	{
-		System: system::{Module, Call, Config, Storage, Event<T>},
-		RandomnessCollectiveFlip: randomness_collective_flip::{Module, Call, Storage},
-		Timestamp: timestamp::{Module, Call, Storage, Inherent},
-		Aura: aura::{Module, Config<T>, Inherent(Timestamp)},
-		Grandpa: grandpa::{Module, Call, Storage, Config, Event},
-		Balances: balances::{Module, Call, Storage, Config<T>, Event<T>},
-		TransactionPayment: transaction_payment::{Module, Storage},
-		Sudo: sudo::{Module, Call, Config<T>, Storage, Event<T>},
-		// Used for the module template in `./template.rs`
-		TemplateModule: template::{Module, Call, Storage, Event<T>},
-		// 引入poe对应模块的操作信息  Module: 模块  Call：调用函数  Storage：存储项  Event<T>：事件  Error不需要额外引入
-		PoeModule: poe::{Module, Call, Storage, Event<T>},
-	}
-);
-
-impl pallet_poe::Trait for Runtime{
-	type Event=Event;
-	type MaxProofLength =MaxProofLength;
-}
 /// The address format for describing accounts.
 pub type Address = sp_runtime::MultiAddress<AccountId, ()>;
 /// Block header type as expected by this runtime.
@@ -467,6 +496,38 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_kitties_rpc_runtime_api::KittiesApi<Block, AccountId, KittyIndex, Balance> for Runtime {
+		fn kitty_traits(kitty_id: KittyIndex) -> Option<pallet_kitties_rpc_runtime_api::KittyTraits> {
+			Kitties::kitty_traits(kitty_id)
+		}
+
+		fn kitties_count() -> KittyIndex {
+			Kitties::kitties_count()
+		}
+
+		fn owned_kitties_count(account: AccountId) -> u64 {
+			u64::from(Kitties::owned_kitties_count(account))
+		}
+
+		fn owned_kitties(
+			account: AccountId,
+			cursor: Option<KittyIndex>,
+			limit: u32,
+		) -> pallet_kitties_rpc_runtime_api::KittiesPage<AccountId, KittyIndex, Balance> {
+			Kitties::owned_kitties_page(&account, cursor, limit)
+		}
+
+		fn kitty_metadata(kitty_id: KittyIndex) -> Option<pallet_kitties_rpc_runtime_api::KittyMetadata> {
+			Kitties::metadata(kitty_id)
+		}
+	}
+
+	impl pallet_poe_rpc_runtime_api::PoeApi<Block> for Runtime {
+		fn verify_batch_leaf(root: [u8; 32], leaf: [u8; 32], index: u32, proof: Vec<[u8; 32]>) -> bool {
+			TemplateModule::verify_batch_leaf(root, leaf, index, proof)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -496,6 +557,8 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+			add_benchmark!(params, batches, pallet_kitties, Kitties);
+			add_benchmark!(params, batches, pallet_poe, TemplateModule);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)